@@ -0,0 +1,48 @@
+//! A sample sys-tui plugin showing a static message
+//!
+//! Build with `cargo build --release` and start sys-tui with
+//! `--plugin-dir examples/hello_plugin/target/release`.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Stylize,
+    widgets::{Block, Paragraph, Widget},
+};
+
+#[allow(dead_code)]
+#[path = "../../../src/plugin/api.rs"]
+mod api;
+
+use api::{PluginWidget, WidgetRegistry};
+
+struct HelloWidget;
+
+impl PluginWidget for HelloWidget {
+    fn name(&self) -> &str {
+        "Hello"
+    }
+
+    fn key_hint(&self) -> &str {
+        "hello from a plugin"
+    }
+
+    fn refresh(&mut self) {}
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new("Hello from hello_plugin!".green())
+            .centered()
+            .block(Block::bordered().title("Hello"))
+            .render(area, buf);
+    }
+}
+
+/// # Safety
+///
+/// `registry` must point to a live `WidgetRegistry`, as passed by sys-tui.
+#[no_mangle]
+pub unsafe extern "C" fn sys_tui_register_widget(registry: *mut WidgetRegistry) {
+    if let Some(registry) = registry.as_mut() {
+        registry.register(Box::new(HelloWidget));
+    }
+}
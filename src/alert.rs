@@ -0,0 +1,271 @@
+//! `--alert-webhook`: POSTs a JSON payload whenever a threshold is crossed, and
+//! `[alert] sound_file`: plays a sound at the same time
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{config::Thresholds, snapshot::SystemSnapshot};
+
+/// At most one webhook per metric is sent within this window
+const DEBOUNCE: Duration = Duration::from_secs(60);
+
+/// Delay before the single retry of a failed POST
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// At most one alert sound is played within this window, whatever the metric
+const SOUND_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The JSON body of an alert webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertPayload {
+    pub hostname: String,
+    /// e.g. `memory`, `disk:/home` or `network:eth0`
+    pub metric: String,
+    pub value: f64,
+    pub threshold: f64,
+    /// unix timestamp in seconds
+    pub timestamp: i64,
+}
+
+/// Watches snapshots for threshold crossings and notifies the webhooks and the sound
+#[derive(Debug)]
+pub struct AlertWebhooks {
+    urls: Vec<String>,
+    sound_file: Option<PathBuf>,
+    /// metrics above their threshold in the last snapshot
+    active: HashSet<String>,
+    last_sent: HashMap<String, Instant>,
+    last_sound: Option<Instant>,
+}
+
+impl AlertWebhooks {
+    pub fn new(urls: Vec<String>, sound_file: Option<PathBuf>) -> Self {
+        AlertWebhooks {
+            urls,
+            sound_file,
+            active: HashSet::new(),
+            last_sent: HashMap::new(),
+            last_sound: None,
+        }
+    }
+
+    /// sends a webhook for every metric that went above its threshold since the last call,
+    /// and plays the sound file for them
+    pub fn check(&mut self, snapshot: &SystemSnapshot, thresholds: &Thresholds) {
+        if self.urls.is_empty() && self.sound_file.is_none() {
+            return;
+        }
+
+        let mut active = HashSet::new();
+        for (metric, value, threshold) in exceeded(snapshot, thresholds) {
+            let debounced = self
+                .last_sent
+                .get(&metric)
+                .is_some_and(|sent| sent.elapsed() < DEBOUNCE);
+
+            if !self.active.contains(&metric) && !debounced {
+                self.last_sent.insert(metric.clone(), Instant::now());
+                self.play_sound();
+                self.send(AlertPayload {
+                    hostname: snapshot.hostname.clone(),
+                    metric: metric.clone(),
+                    value,
+                    threshold,
+                    timestamp: snapshot.timestamp,
+                });
+            }
+            active.insert(metric);
+        }
+
+        self.active = active;
+    }
+
+    fn play_sound(&mut self) {
+        let Some(path) = &self.sound_file else {
+            return;
+        };
+        if self
+            .last_sound
+            .is_some_and(|played| played.elapsed() < SOUND_INTERVAL)
+        {
+            return;
+        }
+
+        self.last_sound = Some(Instant::now());
+        play_alert_sound(path);
+    }
+
+    /// POSTs `payload` to every webhook on a background thread
+    fn send(&self, payload: AlertPayload) {
+        let Ok(body) = serde_json::to_string(&payload) else {
+            return;
+        };
+
+        for url in self.urls.clone() {
+            let body = body.clone();
+
+            thread::spawn(move || {
+                if let Err(err) = post(&url, &body) {
+                    log_error(&url, &err);
+                    thread::sleep(RETRY_DELAY);
+                    if let Err(err) = post(&url, &body) {
+                        log_error(&url, &format!("retry: {err}"));
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// plays `path` with the platform's player without waiting for it, ringing the terminal
+/// bell instead if that fails
+pub fn play_alert_sound(path: &Path) {
+    let played = players(path).into_iter().any(|mut player| {
+        let spawned = player
+            // the TUI owns our terminal
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match spawned {
+            Ok(mut child) => {
+                // reaps it once done; a file it cannot play still gets the bell
+                thread::spawn(move || {
+                    if !child.wait().is_ok_and(|status| status.success()) {
+                        ring_bell();
+                    }
+                });
+                true
+            }
+            Err(_) => false,
+        }
+    });
+
+    if !played {
+        ring_bell();
+    }
+}
+
+/// commands that play `path`, in order of preference
+fn players(path: &Path) -> Vec<Command> {
+    let player = |program: &str, args: &[&str]| {
+        let mut command = Command::new(program);
+        command.args(args).arg(path);
+        command
+    };
+
+    if cfg!(target_os = "macos") {
+        vec![player("afplay", &[])]
+    } else if cfg!(windows) {
+        // plays the system sound rather than the file, which needs no extra player
+        let mut powershell = Command::new("powershell");
+        powershell.args(["-c", "[System.Media.SystemSounds]::Exclamation.Play()"]);
+        vec![powershell]
+    } else {
+        // PulseAudio, then plain ALSA
+        vec![player("paplay", &[]), player("aplay", &["-q"])]
+    }
+}
+
+fn ring_bell() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+}
+
+/// `(metric, value, threshold)` of everything above its threshold, as highlighted in the UI
+fn exceeded(snapshot: &SystemSnapshot, thresholds: &Thresholds) -> Vec<(String, f64, f64)> {
+    let mut exceeded = vec![];
+    let mut check = |metric: String, value: f64, threshold: f64| {
+        if value > threshold {
+            exceeded.push((metric, value, threshold));
+        }
+    };
+
+    let busiest_core = snapshot
+        .cpus
+        .iter()
+        .map(|cpu| cpu.usage)
+        .fold(0.0, f32::max);
+    check(
+        "cpu".to_string(),
+        f64::from(busiest_core),
+        f64::from(thresholds.cpu),
+    );
+
+    let memory = &snapshot.memory;
+    if memory.total > 0 {
+        let used_pct = memory.used as f64 / memory.total as f64 * 100.0;
+        check("memory".to_string(), used_pct, thresholds.memory);
+    }
+
+    for partition in &snapshot.disks {
+        check(
+            format!("disk:{}", partition.mount_point.display()),
+            partition.used_pct(),
+            thresholds.disk,
+        );
+    }
+
+    for interface in &snapshot.networks {
+        let mib_per_sec = (interface.rx_bps + interface.tx_bps) / 1024.0 / 1024.0;
+        check(
+            format!("network:{}", interface.name),
+            mib_per_sec,
+            thresholds.network,
+        );
+    }
+
+    if let Some(ping) = &snapshot.ping {
+        check(
+            "packet_loss".to_string(),
+            ping.loss_pct,
+            thresholds.packet_loss,
+        );
+    }
+
+    exceeded
+}
+
+/// non-2xx responses count as failures, like network errors
+fn post(url: &str, body: &str) -> Result<(), String> {
+    ureq::post(url)
+        .timeout(REQUEST_TIMEOUT)
+        .set("Content-Type", "application/json")
+        .send_string(body)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// appends to `alert-errors.log` in the data directory; there is no UI to report to
+fn log_error(url: &str, message: &str) {
+    let Some(dir) = crate::config::data_dir() else {
+        return;
+    };
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+
+    if let Ok(mut log) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("alert-errors.log"))
+    {
+        let _ = writeln!(
+            log,
+            "{} POST {url} failed: {message}",
+            chrono::Local::now().to_rfc3339()
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! `--api`: a JSON REST API with the data of each widget
+
+use std::{
+    io,
+    net::SocketAddr,
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{process::ProcessStats, snapshot::SharedSnapshot};
+
+/// Processes listed by `/api/processes` without a `limit`
+const DEFAULT_PROCESS_LIMIT: usize = 20;
+
+#[derive(Debug, Serialize)]
+struct Health {
+    status: &'static str,
+    /// how long the API has been serving
+    uptime_secs: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct ApiError {
+    error: String,
+}
+
+/// binds `addr` and serves the API on a background thread
+pub fn spawn(addr: SocketAddr, snapshot: SharedSnapshot) -> io::Result<()> {
+    let server = Server::http(addr).map_err(io::Error::other)?;
+    let started = Instant::now();
+
+    // every response is a quick copy of the snapshot, so one thread serves them all
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let _ = handle(request, &snapshot, started.elapsed());
+        }
+    });
+
+    Ok(())
+}
+
+fn handle(request: Request, snapshot: &SharedSnapshot, uptime: Duration) -> io::Result<()> {
+    // CORS preflight
+    if *request.method() == Method::Options {
+        return request.respond(with_cors(Response::empty(204)));
+    }
+    if *request.method() != Method::Get {
+        return respond(request, 405, &error("only GET is supported"));
+    }
+
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+
+    let Ok(latest) = snapshot.lock().map(|latest| latest.clone()) else {
+        return respond(request, 500, &error("refresh worker panicked"));
+    };
+
+    match path {
+        "/api/cpu" => respond(request, 200, &latest.cpus),
+        "/api/memory" => respond(request, 200, &latest.memory),
+        "/api/disk" => respond(request, 200, &latest.disks),
+        "/api/processes" => match top_processes(latest.processes, query) {
+            Ok(processes) => respond(request, 200, &processes),
+            Err(message) => respond(request, 400, &error(message)),
+        },
+        "/api/health" => respond(
+            request,
+            200,
+            &Health {
+                status: "ok",
+                uptime_secs: uptime.as_secs(),
+            },
+        ),
+        _ => respond(request, 404, &error("not found")),
+    }
+}
+
+/// the processes sorted and cut down as `?limit=20&sort=cpu` asks
+fn top_processes(
+    mut processes: Vec<ProcessStats>,
+    query: &str,
+) -> Result<Vec<ProcessStats>, String> {
+    let mut limit = DEFAULT_PROCESS_LIMIT;
+
+    for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        match key {
+            "limit" => {
+                limit = value
+                    .parse()
+                    .map_err(|_| format!("limit must be a number, got {value:?}"))?
+            }
+            "sort" => match value {
+                "cpu" => processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage)),
+                "memory" => processes.sort_by_key(|process| std::cmp::Reverse(process.memory)),
+                "pid" => processes.sort_by_key(|process| process.pid),
+                "name" => processes.sort_by(|a, b| a.name.cmp(&b.name)),
+                _ => {
+                    return Err(format!(
+                        "sort must be cpu, memory, pid or name, got {value:?}"
+                    ))
+                }
+            },
+            _ => {}
+        }
+    }
+
+    processes.truncate(limit);
+    Ok(processes)
+}
+
+fn error(message: impl Into<String>) -> ApiError {
+    ApiError {
+        error: message.into(),
+    }
+}
+
+fn respond(request: Request, status: u16, body: &impl Serialize) -> io::Result<()> {
+    let json = serde_json::to_string(body)?;
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+
+    request.respond(with_cors(
+        Response::from_string(json)
+            .with_status_code(status)
+            .with_header(content_type),
+    ))
+}
+
+/// lets dashboards on other origins read the API
+fn with_cors<R: io::Read>(response: Response<R>) -> Response<R> {
+    let headers = [
+        ("Access-Control-Allow-Origin", "*"),
+        ("Access-Control-Allow-Methods", "GET, OPTIONS"),
+        ("Access-Control-Allow-Headers", "Content-Type"),
+    ];
+
+    headers
+        .into_iter()
+        .fold(response, |response, (name, value)| {
+            response.with_header(Header::from_bytes(name, value).expect("static header is valid"))
+        })
+}
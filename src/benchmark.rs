@@ -0,0 +1,174 @@
+//! Sequential read benchmark for a single partition
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Instant,
+};
+
+/// Size of each read; also the alignment unit required by `O_DIRECT`
+const BLOCK_SIZE: usize = 1024 * 1024;
+const BLOCK_ALIGN: usize = 4096;
+
+/// Bytes read from a raw device
+const DEVICE_READ_BYTES: u64 = 256 * 1024 * 1024;
+/// Bytes written to and read back from the fallback temp file
+const TEMP_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// `CAP_SYS_RAWIO` from `linux/capability.h`
+#[cfg(target_os = "linux")]
+const CAP_SYS_RAWIO: u32 = 17;
+
+#[derive(Debug)]
+enum BenchmarkEvent {
+    Progress(f64),
+    Finished(io::Result<f64>),
+}
+
+/// A benchmark running on a background thread
+#[derive(Debug)]
+pub struct Benchmark {
+    pub device: String,
+    /// fraction of the read completed, `0.0..=1.0`
+    pub progress: f64,
+    events: Receiver<BenchmarkEvent>,
+}
+
+impl Benchmark {
+    /// starts benchmarking `device`, falling back to a temp file below `mount_point`
+    pub fn start(device: &str, mount_point: &Path) -> Benchmark {
+        let (tx, rx) = mpsc::channel();
+        let device_path = PathBuf::from(device);
+        let mount_point = mount_point.to_path_buf();
+
+        thread::spawn(move || {
+            let result = run(&device_path, &mount_point, &tx);
+            let _ = tx.send(BenchmarkEvent::Finished(result));
+        });
+
+        Benchmark {
+            device: device.to_string(),
+            progress: 0.0,
+            events: rx,
+        }
+    }
+
+    /// drains pending events, returning the throughput in MB/s once the benchmark finished
+    pub fn poll(&mut self) -> Option<io::Result<f64>> {
+        for event in self.events.try_iter() {
+            match event {
+                BenchmarkEvent::Progress(progress) => self.progress = progress,
+                BenchmarkEvent::Finished(result) => return Some(result),
+            }
+        }
+
+        None
+    }
+}
+
+fn run(device: &Path, mount_point: &Path, tx: &Sender<BenchmarkEvent>) -> io::Result<f64> {
+    if has_raw_io() && device.starts_with("/dev") {
+        if let Ok(file) = open_direct(device) {
+            return read_throughput(file, DEVICE_READ_BYTES, tx);
+        }
+    }
+
+    let path = write_temp_file(mount_point)?;
+    let result = open_direct(&path).and_then(|file| read_throughput(file, TEMP_FILE_BYTES, tx));
+    let _ = fs::remove_file(&path);
+
+    result
+}
+
+/// reads up to `limit` bytes in `BLOCK_SIZE` chunks and returns MB/s
+fn read_throughput(mut file: File, limit: u64, tx: &Sender<BenchmarkEvent>) -> io::Result<f64> {
+    // O_DIRECT needs the buffer itself aligned, not just the read size
+    let mut storage = vec![0u8; BLOCK_SIZE + BLOCK_ALIGN];
+    let offset = storage.as_ptr().align_offset(BLOCK_ALIGN);
+    let buf = &mut storage[offset..offset + BLOCK_SIZE];
+
+    let started = Instant::now();
+    let mut total: u64 = 0;
+
+    while total < limit {
+        let read = file.read(buf)?;
+        if read == 0 {
+            break;
+        }
+
+        total += read as u64;
+        let _ = tx.send(BenchmarkEvent::Progress(total as f64 / limit as f64));
+    }
+
+    let secs = started.elapsed().as_secs_f64().max(f64::EPSILON);
+    Ok(total as f64 / 1_000_000.0 / secs)
+}
+
+/// writes the benchmark file on the partition itself, or the system temp dir if it is not writable
+fn write_temp_file(mount_point: &Path) -> io::Result<PathBuf> {
+    let name = format!(".sys-tui-bench-{}", std::process::id());
+    let block = vec![0xa5u8; BLOCK_SIZE];
+
+    let mut last_err = None;
+
+    for dir in [mount_point.to_path_buf(), std::env::temp_dir()] {
+        let path = dir.join(&name);
+
+        let written = File::create(&path).and_then(|mut file| {
+            for _ in 0..TEMP_FILE_BYTES / BLOCK_SIZE as u64 {
+                file.write_all(&block)?;
+            }
+            file.sync_all()
+        });
+
+        match written {
+            Ok(()) => return Ok(path),
+            Err(err) => {
+                let _ = fs::remove_file(&path);
+                last_err = Some(err);
+            }
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| io::Error::other("no writable directory")))
+}
+
+/// opens `path` bypassing the page cache where supported
+#[cfg(target_os = "linux")]
+fn open_direct(path: &Path) -> io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    // tmpfs and some network filesystems reject O_DIRECT
+    OpenOptions::new()
+        .read(true)
+        .custom_flags(nix::libc::O_DIRECT)
+        .open(path)
+        .or_else(|_| OpenOptions::new().read(true).open(path))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn open_direct(path: &Path) -> io::Result<File> {
+    OpenOptions::new().read(true).open(path)
+}
+
+/// whether the effective capability set contains `CAP_SYS_RAWIO`
+#[cfg(target_os = "linux")]
+fn has_raw_io() -> bool {
+    let Ok(status) = fs::read_to_string("/proc/self/status") else {
+        return false;
+    };
+
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapEff:"))
+        .and_then(|caps| u64::from_str_radix(caps.trim(), 16).ok())
+        .is_some_and(|caps| caps & (1 << CAP_SYS_RAWIO) != 0)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn has_raw_io() -> bool {
+    false
+}
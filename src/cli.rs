@@ -0,0 +1,70 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use clap::Parser;
+
+use crate::once;
+
+/// A terminal system monitor
+#[derive(Debug, Parser)]
+#[command(name = "sys-tui", version)]
+pub struct Cli {
+    /// serve a live HTML dashboard on this address, e.g. 127.0.0.1:8080
+    #[arg(long, value_name = "ADDR")]
+    pub web: Option<SocketAddr>,
+
+    /// serve a JSON REST API under /api on this address, e.g. 127.0.0.1:8081
+    #[arg(long, value_name = "ADDR")]
+    pub api: Option<SocketAddr>,
+
+    /// broadcast snapshots as JSON to WebSocket clients on this address
+    #[arg(long, value_name = "ADDR")]
+    pub ws: Option<SocketAddr>,
+
+    /// append WebSocket connect and disconnect events to this file
+    #[arg(long, value_name = "PATH", requires = "ws")]
+    pub ws_log: Option<PathBuf>,
+
+    /// load widget plugins (`.so` / `.dylib`) from this directory
+    #[arg(long, value_name = "PATH")]
+    pub plugin_dir: Option<PathBuf>,
+
+    /// measure round trip time and packet loss to this host
+    #[arg(long, value_name = "HOST")]
+    pub ping: Option<String>,
+
+    /// print one line of stats and exit instead of starting the TUI
+    #[arg(long)]
+    pub once: bool,
+
+    /// template for `--once`, with {cpu_avg}, {mem_used_gb}, {mem_total_gb}, {mem_pct}, {uptime} and {hostname}
+    #[arg(long, value_name = "TEMPLATE", requires = "once", default_value = once::DEFAULT_TEMPLATE)]
+    pub once_format: String,
+
+    /// time DNS resolution of these comma separated hostnames every 30 seconds
+    #[arg(long, value_name = "HOSTS", value_delimiter = ',')]
+    pub dns_check: Vec<String>,
+
+    /// POST a JSON alert to this URL when a threshold is crossed; may be repeated
+    #[arg(long, value_name = "URL")]
+    pub alert_webhook: Vec<String>,
+
+    /// restart sys-tui whenever it exits with an error
+    #[arg(long)]
+    pub watchdog: bool,
+
+    /// check the config, the arguments and which metrics this host provides, then exit
+    #[arg(long, conflicts_with = "once")]
+    pub dry_run: bool,
+
+    /// print the effective config, defaults included, as TOML and exit
+    #[arg(long, conflicts_with_all = ["once", "dry_run"])]
+    pub dump_config: bool,
+
+    /// stop refreshing while the terminal window does not have focus
+    #[arg(long)]
+    pub refresh_on_focus: bool,
+
+    /// print how long each startup phase took to stderr before starting the TUI
+    #[arg(long)]
+    pub verbose: bool,
+}
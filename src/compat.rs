@@ -0,0 +1,44 @@
+//! Which metrics sysinfo actually delivers on this OS and kernel
+//!
+//! Unsupported platforms mostly return empty lists or zeros rather than errors,
+//! so every capability is probed once at startup and judged by its result.
+
+use sysinfo::{Components, CpuRefreshKind, Disks, Networks, RefreshKind, System};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CapabilitySet {
+    /// at least one component reports a temperature above zero
+    pub temperature: bool,
+    /// any hardware components (sensors) are listed at all
+    pub components: bool,
+    /// at least one partition reports a size
+    pub disks: bool,
+    pub networks: bool,
+    /// the load average is not all zeros, which is what Windows reports
+    pub load_average: bool,
+    pub cpu_frequency: bool,
+}
+
+/// probes each capability by calling into sysinfo once
+pub fn detect() -> CapabilitySet {
+    let components = Components::new_with_refreshed_list();
+    let load = System::load_average();
+    let system = System::new_with_specifics(
+        RefreshKind::new().with_cpu(CpuRefreshKind::new().with_frequency()),
+    );
+
+    CapabilitySet {
+        temperature: components
+            .list()
+            .iter()
+            .any(|component| component.temperature() > 0.0),
+        components: !components.list().is_empty(),
+        disks: Disks::new_with_refreshed_list()
+            .list()
+            .iter()
+            .any(|disk| disk.total_space() > 0),
+        networks: !Networks::new_with_refreshed_list().list().is_empty(),
+        load_average: load.one > 0.0 || load.five > 0.0 || load.fifteen > 0.0,
+        cpu_frequency: system.cpus().iter().any(|cpu| cpu.frequency() > 0),
+    }
+}
@@ -0,0 +1,373 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{layout::LayoutMode, widgets::gradient::GradientPalette};
+
+/// User configuration loaded from `~/.config/sys-tui/config.toml`
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub thresholds: Thresholds,
+    pub cpu: CpuConfig,
+    pub processes: ProcessesConfig,
+    pub performance: PerformanceConfig,
+    pub layout: LayoutConfig,
+    pub title_bar: TitleBarConfig,
+    pub alert: AlertConfig,
+    /// keys replayed after the first refresh, e.g. `"2/eth\n"`
+    pub startup_macro: Option<String>,
+    /// highlight values that changed significantly since the previous refresh
+    pub diff_highlight: bool,
+    pub diff_threshold: DiffThreshold,
+    /// `[[watch]]` entries, processes that can be sent a reload signal
+    pub watch: Vec<WatchConfig>,
+    /// `[[launch]]` entries, commands run with `Ctrl+<digit>`
+    pub launch: Vec<LaunchConfig>,
+    /// `[[gradient]]` stops (`at` percent, `color`) that sparkline bars are colored by
+    pub gradient: GradientPalette,
+}
+
+/// Alert thresholds; values above these are highlighted
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Thresholds {
+    /// per-core usage in percent
+    pub cpu: f32,
+    /// used physical memory in percent
+    pub memory: f64,
+    /// combined rx + tx throughput of one interface in MiB/s
+    pub network: f64,
+    /// used space of one partition in percent
+    pub disk: f64,
+    /// `--ping` packet loss in percent
+    pub packet_loss: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            cpu: 90.0,
+            memory: 90.0,
+            network: 100.0,
+            disk: 90.0,
+            packet_loss: 5.0,
+        }
+    }
+}
+
+/// How much a value has to change to be highlighted by `diff_highlight`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DiffThreshold {
+    /// change of percentage values, in percentage points
+    pub points: f32,
+    /// change of absolute values such as throughput, in percent of the old value
+    pub relative: f32,
+    /// smallest change of absolute values that is highlighted, in their own unit
+    /// (bytes per second for throughput), so values near 0 do not flicker
+    pub absolute: f32,
+}
+
+impl Default for DiffThreshold {
+    fn default() -> Self {
+        DiffThreshold {
+            points: 10.0,
+            relative: 20.0,
+            absolute: 1024.0,
+        }
+    }
+}
+
+/// The `[cpu]` table
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CpuConfig {
+    /// narrowest per-core column in characters before the view wraps into more rows
+    pub min_column_width: u16,
+    /// upper bound of columns per row, regardless of width
+    pub max_columns: Option<usize>,
+}
+
+impl Default for CpuConfig {
+    fn default() -> Self {
+        CpuConfig {
+            min_column_width: 8,
+            max_columns: None,
+        }
+    }
+}
+
+/// The `[processes]` table
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProcessesConfig {
+    /// columns of the process table, in order
+    pub columns: Vec<ProcessColumn>,
+    /// processes whose name starts with one of these are hidden, e.g. `"kworker"`
+    pub hide_names: Vec<String>,
+    /// hide processes without a command line; toggled at runtime with `K`
+    pub hide_kernel_threads: bool,
+    /// seconds between process list refreshes, independent of the 1 second UI refresh
+    pub refresh_interval_secs: f64,
+    /// terminal emulator `o` opens top / htop in, e.g. `"xterm"`; `$TERMINAL` if unset
+    pub drill_terminal: Option<String>,
+}
+
+impl Default for ProcessesConfig {
+    fn default() -> Self {
+        ProcessesConfig {
+            columns: vec![
+                ProcessColumn::Pid,
+                ProcessColumn::Name,
+                ProcessColumn::Cpu,
+                ProcessColumn::Memory,
+            ],
+            hide_names: vec![],
+            hide_kernel_threads: false,
+            refresh_interval_secs: 2.0,
+            drill_terminal: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessColumn {
+    Pid,
+    Name,
+    Cpu,
+    Memory,
+    /// voluntary context switches per second
+    Vcs,
+    /// involuntary context switches per second
+    Nvcs,
+    /// cores the process may run on
+    Affinity,
+}
+
+/// Keys bound globally or on the Processes tab, which a `[[watch]]` reload key must not shadow
+///
+/// Kept in sync with `App::handle_key_event` by a test in `main.rs`.
+pub const RESERVED_KEYS: &str = "qfpPLnE/a123456789vmoKu";
+
+/// One `[[watch]]` entry
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct WatchConfig {
+    pub pid: u32,
+    pub reload_signal: ReloadSignal,
+    /// sends `reload_signal` while `pid` is selected in the process table
+    pub reload_key: char,
+}
+
+/// One `[[launch]]` entry
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LaunchConfig {
+    /// the digit pressed with Ctrl
+    pub key: char,
+    /// run with `sh -c` while the TUI is suspended, e.g. `"htop"`
+    pub command: String,
+}
+
+/// Signals that the `[[watch]]` reload key can send; anything else is rejected at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ReloadSignal {
+    #[serde(rename = "SIGUSR1")]
+    Usr1,
+    #[serde(rename = "SIGUSR2")]
+    Usr2,
+    #[serde(rename = "SIGHUP")]
+    Hup,
+    #[serde(rename = "SIGWINCH")]
+    Winch,
+}
+
+impl ReloadSignal {
+    pub fn name(self) -> &'static str {
+        match self {
+            ReloadSignal::Usr1 => "SIGUSR1",
+            ReloadSignal::Usr2 => "SIGUSR2",
+            ReloadSignal::Hup => "SIGHUP",
+            ReloadSignal::Winch => "SIGWINCH",
+        }
+    }
+}
+
+/// The `[layout]` table
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    /// fixed layout tier; picked from the terminal height when unset
+    pub mode: Option<LayoutMode>,
+    /// below this many columns only a "terminal too small" notice is drawn
+    pub min_width: u16,
+    /// below this many rows only a "terminal too small" notice is drawn
+    pub min_height: u16,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        LayoutConfig {
+            mode: None,
+            min_width: 80,
+            min_height: 24,
+        }
+    }
+}
+
+/// The `[title_bar]` table
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TitleBarConfig {
+    /// window title while `n` is on, with the `--once-format` placeholders
+    pub format: String,
+}
+
+impl Default for TitleBarConfig {
+    fn default() -> Self {
+        TitleBarConfig {
+            format: "sys-tui: CPU:{cpu_avg}% MEM:{mem_pct}% {hostname}".to_string(),
+        }
+    }
+}
+
+/// The `[alert]` table
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AlertConfig {
+    /// WAV / OGG file played when a threshold is crossed, instead of the terminal bell
+    pub sound_file: Option<PathBuf>,
+}
+
+/// The `[performance]` table
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PerformanceConfig {
+    /// core sys-tui pins itself to when `P` is pressed
+    pub pin_to_core: usize,
+}
+
+impl Config {
+    /// loads the config from the default location, falling back to defaults if it does not exist
+    pub fn load() -> io::Result<Config> {
+        match default_path() {
+            Some(path) if path.exists() => Config::load_from(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> io::Result<Config> {
+        let contents = std::fs::read_to_string(path)?;
+
+        let invalid = |message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {message}", path.display()),
+            )
+        };
+
+        let config: Config = toml::from_str(&contents).map_err(|err| invalid(err.to_string()))?;
+        config.validate().map_err(invalid)?;
+
+        Ok(config)
+    }
+
+    /// the config as TOML that [`Config::load_from`] reads back
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+
+    /// writes the config to `path`, replacing the file and any comments in it
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let contents = self.to_toml().map_err(io::Error::other)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        std::fs::write(path, contents)
+    }
+
+    /// checks values that parse fine but make no sense
+    fn validate(&self) -> Result<(), String> {
+        if self.cpu.min_column_width < 3 {
+            return Err(format!(
+                "cpu.min_column_width must be at least 3, got {}",
+                self.cpu.min_column_width
+            ));
+        }
+        if self.cpu.max_columns == Some(0) {
+            return Err("cpu.max_columns must be at least 1".to_string());
+        }
+        let process_interval = self.processes.refresh_interval_secs;
+        if !(process_interval.is_finite() && process_interval > 0.0) {
+            return Err(format!(
+                "processes.refresh_interval_secs must be positive, got {process_interval}"
+            ));
+        }
+        if let Some(watch) = self
+            .watch
+            .iter()
+            .find(|watch| RESERVED_KEYS.contains(watch.reload_key))
+        {
+            return Err(format!(
+                "watch.reload_key {:?} is already bound to another action",
+                watch.reload_key
+            ));
+        }
+        if let Some(launch) = self
+            .launch
+            .iter()
+            .find(|launch| !launch.key.is_ascii_digit())
+        {
+            return Err(format!("launch.key must be a digit, got {:?}", launch.key));
+        }
+
+        Ok(())
+    }
+}
+
+/// `$XDG_CONFIG_HOME/sys-tui/config.toml`, or `~/.config/sys-tui/config.toml`
+pub fn default_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("sys-tui").join("config.toml"))
+}
+
+/// `$XDG_DATA_HOME/sys-tui`, or `~/.local/share/sys-tui`, for state sys-tui writes itself
+pub fn data_dir() -> Option<PathBuf> {
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+        })?;
+
+    Some(data_dir.join("sys-tui"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_reload_key(key: char) -> Config {
+        toml::from_str(&format!(
+            "[[watch]]\npid = 1\nreload_signal = \"SIGHUP\"\nreload_key = \"{key}\"\n"
+        ))
+        .expect("config parses")
+    }
+
+    #[test]
+    fn rejects_reload_keys_bound_to_other_actions() {
+        assert!(with_reload_key('q').validate().is_err());
+        assert!(with_reload_key('K').validate().is_err());
+    }
+
+    #[test]
+    fn accepts_free_reload_keys() {
+        assert!(with_reload_key('r').validate().is_ok());
+    }
+}
@@ -0,0 +1,132 @@
+//! CPU cache topology from `/sys/devices/system/cpu/cpuN/cache`
+//!
+//! Only Linux has these files; elsewhere every core reports no caches.
+
+use std::{fs, path::Path};
+
+/// One cache a core can use, e.g. its L1 data cache
+#[derive(Debug, Clone)]
+pub struct CacheLevel {
+    pub level: u8,
+    /// `Data`, `Instruction` or `Unified`
+    pub kind: String,
+    pub size_bytes: u64,
+    /// cores sharing this cache, e.g. `0-7`
+    pub shared_cpu_list: String,
+}
+
+impl CacheLevel {
+    /// `L1d`, `L1i`, `L2`, ...
+    pub fn name(&self) -> String {
+        match self.kind.as_str() {
+            "Data" => format!("L{}d", self.level),
+            "Instruction" => format!("L{}i", self.level),
+            _ => format!("L{}", self.level),
+        }
+    }
+
+    /// the number of logical CPUs in `shared_cpu_list`, e.g. 4 for `0-1,8-9`
+    pub fn shared_cpus(&self) -> usize {
+        self.shared_cpu_list
+            .split(',')
+            .filter_map(|range| match range.split_once('-') {
+                Some((first, last)) => {
+                    Some(last.parse::<usize>().ok()? + 1 - first.parse::<usize>().ok()?)
+                }
+                None => range.parse::<usize>().ok().map(|_| 1),
+            })
+            .sum()
+    }
+}
+
+/// the caches of `core`, innermost first; empty if sysfs does not describe them
+pub fn read_cache_info(core: usize) -> Vec<CacheLevel> {
+    let dir = format!("/sys/devices/system/cpu/cpu{core}/cache");
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return vec![];
+    };
+
+    let mut levels: Vec<CacheLevel> = entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("index"))
+        .filter_map(|entry| read_level(&entry.path()))
+        .collect();
+
+    levels.sort_by(|a, b| a.level.cmp(&b.level).then(a.kind.cmp(&b.kind)));
+    levels
+}
+
+/// the caches of every core sysfs lists, indexed by core
+pub fn read_all() -> Vec<Vec<CacheLevel>> {
+    (0..)
+        .map_while(|core| {
+            Path::new(&format!("/sys/devices/system/cpu/cpu{core}"))
+                .exists()
+                .then(|| read_cache_info(core))
+        })
+        .collect()
+}
+
+fn read_level(dir: &Path) -> Option<CacheLevel> {
+    let read = |name: &str| {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .map(|value| value.trim().to_string())
+    };
+
+    Some(CacheLevel {
+        level: read("level")?.parse().ok()?,
+        kind: read("type")?,
+        size_bytes: parse_size(&read("size")?)?,
+        shared_cpu_list: read("shared_cpu_list").unwrap_or_default(),
+    })
+}
+
+/// sysfs sizes look like `32K` or `16M`
+fn parse_size(size: &str) -> Option<u64> {
+    let (digits, multiplier) = match size.as_bytes().last()? {
+        b'K' => (&size[..size.len() - 1], 1024),
+        b'M' => (&size[..size.len() - 1], 1024 * 1024),
+        _ => (size, 1),
+    };
+
+    Some(digits.parse::<u64>().ok()? * multiplier)
+}
+
+/// e.g. `L1d: 32 KiB | L1i: 32 KiB | L2: 256 KiB | L3: 16 MiB (shared)`
+///
+/// A cache counts as shared when more CPUs use it than the innermost one, so
+/// hyperthread siblings alone do not make a cache shared.
+pub fn summary(levels: &[CacheLevel]) -> String {
+    let private_cpus = levels.first().map_or(1, CacheLevel::shared_cpus);
+
+    levels
+        .iter()
+        .map(|cache| {
+            let shared = if cache.shared_cpus() > private_cpus {
+                " (shared)"
+            } else {
+                ""
+            };
+            format!(
+                "{}: {}{shared}",
+                cache.name(),
+                format_size(cache.size_bytes)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+/// whole binary units as in sysfs, `1.5 MiB` only where the size is not a multiple
+fn format_size(bytes: u64) -> String {
+    const MIB: u64 = 1024 * 1024;
+
+    if bytes >= MIB && bytes.is_multiple_of(MIB) {
+        format!("{} MiB", bytes / MIB)
+    } else if bytes >= MIB {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    } else {
+        format!("{} KiB", bytes / 1024)
+    }
+}
@@ -0,0 +1,45 @@
+//! Per-core temperatures from the sensors sysinfo lists, for the CPU tab's heatmap
+
+use ratatui::style::Color;
+use sysinfo::Components;
+
+/// temperature of each core by its number, `None` for cores without a sensor;
+/// empty where no sensor is labelled per core, e.g. with AMD's single `Tctl`
+pub fn core_temperatures(components: &Components) -> Vec<Option<f32>> {
+    let mut temperatures: Vec<Option<f32>> = vec![];
+
+    for component in components.list() {
+        let Some(core) = core_index(component.label()) else {
+            continue;
+        };
+        let celsius = component.temperature();
+        if !celsius.is_finite() || celsius <= 0.0 {
+            continue;
+        }
+
+        if temperatures.len() <= core {
+            temperatures.resize(core + 1, None);
+        }
+        temperatures[core] = Some(celsius);
+    }
+
+    temperatures
+}
+
+/// the core number of a sensor label like `"coretemp Core 3"`
+fn core_index(label: &str) -> Option<usize> {
+    let (_, rest) = label.split_once("Core ")?;
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+
+    digits.parse().ok()
+}
+
+/// blue below 40 °C, green to 60 °C, yellow to 75 °C and red above
+pub fn temperature_color(celsius: f32) -> Color {
+    match celsius {
+        t if t < 40.0 => Color::Blue,
+        t if t < 60.0 => Color::Green,
+        t if t <= 75.0 => Color::Yellow,
+        _ => Color::Red,
+    }
+}
@@ -0,0 +1,120 @@
+//! Which logical CPUs are hyperthreads of the same physical core
+//!
+//! Read from `/sys/devices/system/cpu/cpuN/topology` once at startup; other
+//! platforms have no topology, so anything derived from it is hidden there.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::snapshot::CpuSnapshot;
+
+/// Logical CPUs grouped by the physical core they run on
+#[derive(Debug, Clone)]
+pub struct CpuTopology {
+    /// indices of the logical CPUs of each physical core
+    cores: Vec<Vec<usize>>,
+    logical_cpus: usize,
+}
+
+impl CpuTopology {
+    /// `None` if any CPU lacks its package or core ID, rather than guessing
+    pub fn detect() -> Option<CpuTopology> {
+        let mut cores: BTreeMap<(u32, u32), Vec<usize>> = BTreeMap::new();
+        let mut logical_cpus = 0;
+
+        for cpu in 0.. {
+            let dir = format!("/sys/devices/system/cpu/cpu{cpu}");
+            if !Path::new(&dir).exists() {
+                break;
+            }
+
+            let read = |name: &str| -> Option<u32> {
+                fs::read_to_string(format!("{dir}/topology/{name}"))
+                    .ok()?
+                    .trim()
+                    .parse()
+                    .ok()
+            };
+            let package = read("physical_package_id")?;
+            let core = read("core_id")?;
+
+            cores.entry((package, core)).or_default().push(cpu);
+            logical_cpus += 1;
+        }
+
+        (logical_cpus > 0).then(|| CpuTopology {
+            cores: cores.into_values().collect(),
+            logical_cpus,
+        })
+    }
+
+    pub fn physical_cores(&self) -> usize {
+        self.cores.len()
+    }
+
+    pub fn logical_cpus(&self) -> usize {
+        self.logical_cpus
+    }
+}
+
+/// the work of the physical cores as a share of the logical CPUs the OS counts
+///
+/// Hyperthread siblings share one core's execution units, so a core does at
+/// most one core's worth of work, the average of its siblings. Summed over the
+/// cores and divided by the logical CPUs, every thread at 100 % on 2-way SMT
+/// reads 50 %; without SMT this is the plain mean.
+pub fn physical_cpu_usage(cpus: &[CpuSnapshot], topology: &CpuTopology) -> f32 {
+    if topology.logical_cpus == 0 {
+        return 0.0;
+    }
+
+    let busy_cores: f32 = topology
+        .cores
+        .iter()
+        .map(|siblings| {
+            let usage: f32 = siblings
+                .iter()
+                .filter_map(|&index| Some(cpus.get(index)?.usage))
+                .sum();
+            usage / siblings.len() as f32
+        })
+        .sum();
+
+    busy_cores / topology.logical_cpus as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cpus(usages: &[f32]) -> Vec<CpuSnapshot> {
+        usages
+            .iter()
+            .map(|&usage| CpuSnapshot {
+                usage,
+                ..CpuSnapshot::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fully_loaded_smt_is_half_physical() {
+        let topology = CpuTopology {
+            cores: vec![vec![0, 2], vec![1, 3]],
+            logical_cpus: 4,
+        };
+
+        let usage = physical_cpu_usage(&cpus(&[100.0; 4]), &topology);
+        assert!((usage - 50.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn without_smt_is_the_mean() {
+        let topology = CpuTopology {
+            cores: vec![vec![0], vec![1]],
+            logical_cpus: 2,
+        };
+
+        let usage = physical_cpu_usage(&cpus(&[100.0, 50.0]), &topology);
+        assert!((usage - 75.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,317 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use sysinfo::Disks;
+
+use crate::nvme::{EnduranceCache, NvmeEndurance};
+
+/// Space usage for one mounted partition
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskStats {
+    /// device name as reported by the OS, e.g. `/dev/nvme0n1p2`
+    pub name: String,
+    pub mount_point: PathBuf,
+    pub file_system: String,
+    pub total: u64,
+    pub available: u64,
+    /// `(used, total)` inodes, `None` where the filesystem or platform does not report them
+    pub inodes: Option<(u64, u64)>,
+    /// write endurance left, only reported for NVMe drives
+    pub endurance: Option<NvmeEndurance>,
+    /// throughput since the previous refresh, only reported on Linux
+    pub io: Option<DiskIo>,
+    /// sorted, empty where the platform does not report them
+    pub mount_options: Vec<MountOption>,
+}
+
+/// Read and write throughput of one partition
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct DiskIo {
+    pub read_bps: f64,
+    pub write_bps: f64,
+}
+
+impl DiskStats {
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+
+    pub fn used_pct(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.used() as f64 / self.total as f64 * 100.0
+    }
+
+    pub fn inodes_used_pct(&self) -> Option<f64> {
+        match self.inodes {
+            Some((used, total)) if total > 0 => Some(used as f64 / total as f64 * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// collects usage for every mounted partition, sorted by mount point
+pub fn collect(
+    disks: &Disks,
+    endurance: &mut EnduranceCache,
+    inodes: &mut InodeCache,
+) -> Vec<DiskStats> {
+    let mount_points: Vec<&Path> = disks.list().iter().map(|disk| disk.mount_point()).collect();
+    let mount_options = read_mount_options(&mount_points);
+
+    let mut stats: Vec<DiskStats> = disks
+        .list()
+        .iter()
+        .map(|disk| DiskStats {
+            name: disk.name().to_string_lossy().into_owned(),
+            mount_point: disk.mount_point().to_path_buf(),
+            file_system: disk.file_system().to_string_lossy().into_owned(),
+            total: disk.total_space(),
+            available: disk.available_space(),
+            inodes: inodes.get(disk.mount_point()),
+            endurance: endurance.get(&disk.name().to_string_lossy()),
+            io: None,
+            mount_options: mount_options
+                .get(disk.mount_point())
+                .cloned()
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    stats.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    stats
+}
+
+/// A mount option of a partition, as in `/proc/mounts`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MountOption {
+    ReadOnly,
+    NoExec,
+    NoSuid,
+    Relatime,
+    Async,
+    Other(String),
+}
+
+impl MountOption {
+    fn parse(option: &str) -> MountOption {
+        match option {
+            "ro" => MountOption::ReadOnly,
+            "noexec" => MountOption::NoExec,
+            "nosuid" => MountOption::NoSuid,
+            "relatime" => MountOption::Relatime,
+            "async" => MountOption::Async,
+            other => MountOption::Other(other.to_string()),
+        }
+    }
+
+    /// the option as `mount` spells it
+    pub fn label(&self) -> &str {
+        match self {
+            MountOption::ReadOnly => "ro",
+            MountOption::NoExec => "noexec",
+            MountOption::NoSuid => "nosuid",
+            MountOption::Relatime => "relatime",
+            MountOption::Async => "async",
+            MountOption::Other(option) => option,
+        }
+    }
+}
+
+/// serialized as its label, e.g. `"ro"`
+impl Serialize for MountOption {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.label())
+    }
+}
+
+/// the sorted options each of `mount_points` is mounted with; missing if it is not
+/// mounted or the platform does not report them
+///
+/// `/proc/mounts` is read once for all of them. If something is mounted over a
+/// mount point, the topmost mount (listed last) counts.
+#[cfg(target_os = "linux")]
+pub fn read_mount_options(mount_points: &[&Path]) -> HashMap<PathBuf, Vec<MountOption>> {
+    let Ok(mounts) = std::fs::read_to_string("/proc/mounts") else {
+        return HashMap::new();
+    };
+
+    let mut options_by_mount = HashMap::new();
+    for line in mounts.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(target), Some(options)) = (fields.nth(1), fields.nth(1)) else {
+            continue;
+        };
+
+        // spaces and tabs in paths are octal escapes
+        let target = PathBuf::from(
+            target
+                .replace("\\040", " ")
+                .replace("\\011", "\t")
+                .replace("\\134", "\\"),
+        );
+        if !mount_points.contains(&target.as_path()) {
+            continue;
+        }
+
+        let mut options: Vec<MountOption> = options
+            .split(',')
+            .map(MountOption::parse)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        options.sort();
+        options_by_mount.insert(target, options);
+    }
+
+    options_by_mount
+}
+
+/// the flags `statvfs` reports, which are fewer than `/proc/mounts` lists
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn read_mount_options(mount_points: &[&Path]) -> HashMap<PathBuf, Vec<MountOption>> {
+    use nix::sys::statvfs::{statvfs, FsFlags};
+
+    mount_points
+        .iter()
+        .filter_map(|mount_point| {
+            let flags = statvfs(*mount_point).ok()?.flags();
+
+            let mut options = vec![if flags.contains(FsFlags::ST_RDONLY) {
+                MountOption::ReadOnly
+            } else {
+                MountOption::Other("rw".to_string())
+            }];
+            if flags.contains(FsFlags::ST_NOSUID) {
+                options.push(MountOption::NoSuid);
+            }
+            options.sort();
+            Some((mount_point.to_path_buf(), options))
+        })
+        .collect()
+}
+
+/// Windows has no mount options
+#[cfg(not(unix))]
+pub fn read_mount_options(_mount_points: &[&Path]) -> HashMap<PathBuf, Vec<MountOption>> {
+    HashMap::new()
+}
+
+/// sets the throughput of every partition found in both `(read, written)` byte counters,
+/// which are keyed by device name without `/dev/`
+pub fn apply_io(
+    stats: &mut [DiskStats],
+    before: &HashMap<String, (u64, u64)>,
+    after: &HashMap<String, (u64, u64)>,
+    elapsed: Duration,
+) {
+    for partition in stats {
+        let device = partition.name.trim_start_matches("/dev/");
+        if let (Some(&(read_before, written_before)), Some(&(read, written))) =
+            (before.get(device), after.get(device))
+        {
+            partition.io = Some(DiskIo {
+                read_bps: crate::process::per_sec(read_before, read, elapsed),
+                write_bps: crate::process::per_sec(written_before, written, elapsed),
+            });
+        }
+    }
+}
+
+/// How long a refresh waits for `statvfs` before showing the mount's inodes as N/A
+const INODE_READ_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// How long inode counts are reused before `statvfs` is called again
+const INODE_CACHE_TTL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+enum InodeRead {
+    Done(Instant, Option<(u64, u64)>),
+    /// a `statvfs` that did not return in time, e.g. on an unreachable NFS server
+    Hung(Receiver<Option<(u64, u64)>>),
+}
+
+/// Inode counts by mount point, read off the refresh path so a hung mount cannot block it
+#[derive(Debug, Default)]
+pub struct InodeCache {
+    entries: HashMap<PathBuf, InodeRead>,
+}
+
+impl InodeCache {
+    /// the inodes of the filesystem mounted at `mount_point`, `None` while its `statvfs` hangs
+    pub fn get(&mut self, mount_point: &Path) -> Option<(u64, u64)> {
+        match self.entries.get(mount_point) {
+            Some(InodeRead::Done(read_at, inodes)) if read_at.elapsed() < INODE_CACHE_TTL => {
+                return *inodes
+            }
+            // a hung mount keeps its one blocked thread instead of piling up new ones
+            Some(InodeRead::Hung(receiver)) => match receiver.try_recv() {
+                Ok(inodes) => {
+                    self.done(mount_point, inodes);
+                    return inodes;
+                }
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    self.done(mount_point, None);
+                    return None;
+                }
+            },
+            _ => {}
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let path = mount_point.to_path_buf();
+        thread::spawn(move || {
+            let _ = sender.send(read_inode_usage(&path));
+        });
+
+        match receiver.recv_timeout(INODE_READ_TIMEOUT) {
+            Ok(inodes) => {
+                self.done(mount_point, inodes);
+                inodes
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                self.entries
+                    .insert(mount_point.to_path_buf(), InodeRead::Hung(receiver));
+                None
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                self.done(mount_point, None);
+                None
+            }
+        }
+    }
+
+    fn done(&mut self, mount_point: &Path, inodes: Option<(u64, u64)>) {
+        self.entries.insert(
+            mount_point.to_path_buf(),
+            InodeRead::Done(Instant::now(), inodes),
+        );
+    }
+}
+
+/// `(used, total)` inodes of the filesystem mounted at `mount_point`
+#[cfg(unix)]
+pub fn read_inode_usage(mount_point: &Path) -> Option<(u64, u64)> {
+    let stats = nix::sys::statvfs::statvfs(mount_point).ok()?;
+
+    let total = stats.files() as u64;
+    let free = stats.files_free() as u64;
+
+    // filesystems without a fixed inode table (btrfs, most FUSE mounts) report 0
+    (total > 0).then(|| (total.saturating_sub(free), total))
+}
+
+/// Windows does not expose inode counts
+#[cfg(not(unix))]
+pub fn read_inode_usage(_mount_point: &Path) -> Option<(u64, u64)> {
+    None
+}
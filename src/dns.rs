@@ -0,0 +1,93 @@
+//! `--dns-check`: periodic resolution time of a list of hostnames
+
+use std::{
+    net::ToSocketAddrs,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The latest result for one host, written by its lookup thread
+pub type SharedDns = Arc<Mutex<DnsResult>>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DnsStatus {
+    /// the first lookup has not finished yet
+    #[default]
+    Pending,
+    Ok,
+    /// the lookup failed, usually because the name does not exist
+    NxDomain,
+    Timeout,
+}
+
+impl DnsStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            DnsStatus::Pending => "...",
+            DnsStatus::Ok => "OK",
+            DnsStatus::NxDomain => "NXDOMAIN",
+            DnsStatus::Timeout => "TIMEOUT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DnsResult {
+    pub host: String,
+    /// duration of the last successful lookup in milliseconds
+    pub resolve_ms: Option<f64>,
+    pub status: DnsStatus,
+}
+
+/// resolves `host` every 30 seconds in a background thread
+pub fn spawn(host: String) -> SharedDns {
+    let shared = Arc::new(Mutex::new(DnsResult {
+        host: host.clone(),
+        ..Default::default()
+    }));
+
+    let result = Arc::clone(&shared);
+    thread::spawn(move || loop {
+        let (resolve_ms, status) = lookup(&host);
+
+        if let Ok(mut result) = result.lock() {
+            result.resolve_ms = resolve_ms;
+            result.status = status;
+        }
+
+        thread::sleep(CHECK_INTERVAL);
+    });
+
+    shared
+}
+
+/// resolves `host` on a helper thread so a hanging resolver only costs that thread
+fn lookup(host: &str) -> (Option<f64>, DnsStatus) {
+    let (tx, rx) = mpsc::channel();
+    let target = (host.to_string(), 0);
+
+    let started = Instant::now();
+    thread::spawn(move || {
+        let _ = tx.send(
+            target
+                .to_socket_addrs()
+                .map(|mut addrs| addrs.next().is_some()),
+        );
+    });
+
+    match rx.recv_timeout(LOOKUP_TIMEOUT) {
+        Ok(Ok(true)) => (
+            Some(started.elapsed().as_secs_f64() * 1000.0),
+            DnsStatus::Ok,
+        ),
+        Ok(_) => (None, DnsStatus::NxDomain),
+        Err(_) => (None, DnsStatus::Timeout),
+    }
+}
@@ -0,0 +1,95 @@
+//! Maps processes to the Docker containers they run in
+//!
+//! The container ID comes from `/proc/<pid>/cgroup` and its name from the
+//! Docker Engine API on `/var/run/docker.sock`. Without the socket (no
+//! Docker, or no permission) every lookup is `None`.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Read, Write},
+    os::unix::net::UnixStream,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// How long a container name (or its absence) is reused
+const CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Upper bound for one API request, so a hung daemon cannot stall the refresh
+const REQUEST_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Container names by container ID, refreshed every [`CACHE_TTL`]
+#[derive(Debug, Default)]
+pub struct ContainerNames {
+    names: HashMap<String, (Instant, Option<String>)>,
+}
+
+impl ContainerNames {
+    /// the name of the container `pid` runs in, without the leading `/`
+    pub fn pid_to_container_name(&mut self, pid: u32) -> Option<String> {
+        let id = container_id(pid)?;
+
+        match self.names.get(&id) {
+            Some((read_at, name)) if read_at.elapsed() < CACHE_TTL => name.clone(),
+            _ => {
+                let name = query_name(&id);
+                self.names.insert(id, (Instant::now(), name.clone()));
+                name
+            }
+        }
+    }
+
+    /// drops expired entries, so containers that are gone do not pile up
+    pub fn prune(&mut self) {
+        self.names
+            .retain(|_, (read_at, _)| read_at.elapsed() < CACHE_TTL);
+    }
+}
+
+/// the 64 hex digit container ID in the cgroup path of `pid`, e.g. from
+/// `0::/system.slice/docker-<id>.scope` or `12:pids:/docker/<id>`
+fn container_id(pid: u32) -> Option<String> {
+    let cgroup = fs::read_to_string(format!("/proc/{pid}/cgroup")).ok()?;
+
+    cgroup
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .filter(|path| path.contains("docker"))
+        .flat_map(|path| path.split(['/', '-', '.']))
+        .find(|part| part.len() == 64 && part.bytes().all(|b| b.is_ascii_hexdigit()))
+        .map(str::to_string)
+}
+
+/// asks the Docker daemon for the name of container `id`
+fn query_name(id: &str) -> Option<String> {
+    if !Path::new(DOCKER_SOCKET).exists() {
+        return None;
+    }
+
+    let mut stream = UnixStream::connect(DOCKER_SOCKET).ok()?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT)).ok()?;
+
+    // HTTP/1.0 makes the daemon close the connection instead of chunking the body
+    write!(
+        stream,
+        "GET /containers/{id}/json HTTP/1.0\r\nHost: docker\r\n\r\n"
+    )
+    .ok()?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).ok()?;
+
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    if !head.starts_with("HTTP/1.0 200") && !head.starts_with("HTTP/1.1 200") {
+        return None;
+    }
+
+    let container: serde_json::Value = serde_json::from_str(body).ok()?;
+    let name = container.get("Name")?.as_str()?;
+
+    Some(name.trim_start_matches('/').to_string())
+}
@@ -0,0 +1,190 @@
+//! `--dry-run`: checks the config, the arguments and what this host can report, then exits
+
+use std::{fs::File, io, path::Path};
+
+use crate::{cli::Cli, compat, config::Config, layout_editor, util::format_bytes, worker};
+
+/// The findings of a dry run, printed in the order they were made
+#[derive(Debug, Default)]
+struct Report {
+    /// `(subject, outcome)` lines
+    checks: Vec<(&'static str, String)>,
+    problems: Vec<String>,
+    missing_permissions: Vec<String>,
+}
+
+impl Report {
+    fn check(&mut self, subject: &'static str, outcome: impl Into<String>) {
+        self.checks.push((subject, outcome.into()));
+    }
+
+    /// records a check that makes the dry run fail
+    fn problem(&mut self, subject: &'static str, message: String) {
+        self.check(subject, format!("error: {message}"));
+        self.problems.push(format!("{subject}: {message}"));
+    }
+
+    /// records whether `path` can be opened, noting a permission error separately
+    fn probe(&mut self, subject: &'static str, path: &str, available: &str) {
+        match File::open(path) {
+            Ok(_) => self.check(subject, available),
+            Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {
+                self.check(subject, format!("no permission to read {path}"));
+                self.missing_permissions
+                    .push(format!("{subject}: read {path}"));
+            }
+            Err(err) => self.check(subject, format!("unavailable ({path}: {err})")),
+        }
+    }
+}
+
+/// runs all checks, prints the report and returns the exit code
+pub fn run(cli: &Cli) -> i32 {
+    let mut report = Report::default();
+
+    check_config(&mut report);
+    check_args(&mut report, cli);
+    check_subsystems(&mut report);
+
+    for (subject, outcome) in &report.checks {
+        println!("{subject:<14}{outcome}");
+    }
+
+    if !report.missing_permissions.is_empty() {
+        println!("\nmissing permissions (run as root to enable):");
+        for permission in &report.missing_permissions {
+            println!("  - {permission}");
+        }
+    }
+
+    if report.problems.is_empty() {
+        println!("\nok");
+        return 0;
+    }
+
+    println!("\n{} problem(s):", report.problems.len());
+    for problem in &report.problems {
+        println!("  - {problem}");
+    }
+    1
+}
+
+fn check_config(report: &mut Report) {
+    match Config::load() {
+        Ok(_) => match crate::config::default_path() {
+            Some(path) if path.exists() => {
+                report.check("config", format!("ok ({})", path.display()))
+            }
+            _ => report.check("config", "ok (defaults, no config file)"),
+        },
+        Err(err) => report.problem("config", err.to_string()),
+    }
+
+    match layout_editor::default_path() {
+        Some(path) if path.exists() => match layout_editor::GridLayout::load(&path) {
+            Ok(_) => report.check("layout", format!("ok ({})", path.display())),
+            Err(err) => report.problem("layout", err.to_string()),
+        },
+        _ => report.check("layout", "ok (default dashboard)"),
+    }
+}
+
+/// what clap cannot check by itself: paths and host names
+fn check_args(report: &mut Report, cli: &Cli) {
+    if let Some(dir) = &cli.plugin_dir {
+        if dir.is_dir() {
+            report.check("--plugin-dir", format!("ok ({})", dir.display()));
+        } else {
+            report.problem(
+                "--plugin-dir",
+                format!("{} is not a directory", dir.display()),
+            );
+        }
+    }
+
+    if let Some(path) = &cli.ws_log {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        if dir.is_dir() {
+            report.check("--ws-log", format!("ok ({})", path.display()));
+        } else {
+            report.problem("--ws-log", format!("{} does not exist", dir.display()));
+        }
+    }
+
+    if cli
+        .ping
+        .as_deref()
+        .is_some_and(|host| host.trim().is_empty())
+    {
+        report.problem("--ping", "empty host name".to_string());
+    }
+    if cli.dns_check.iter().any(|host| host.trim().is_empty()) {
+        report.problem("--dns-check", "empty host name in list".to_string());
+    }
+}
+
+fn check_subsystems(report: &mut Report) {
+    let snapshot = worker::snapshot_once();
+
+    if snapshot.cpus.is_empty() {
+        report.problem("CPU", "no cores reported".to_string());
+    } else {
+        report.check("CPU", format!("ok ({} cores)", snapshot.cpus.len()));
+    }
+
+    if snapshot.memory.total == 0 {
+        report.problem("memory", "no memory reported".to_string());
+    } else {
+        report.check(
+            "memory",
+            format!("ok ({})", format_bytes(snapshot.memory.total)),
+        );
+    }
+
+    report.check("disks", format!("ok ({} partitions)", snapshot.disks.len()));
+    report.check(
+        "network",
+        format!("ok ({} interfaces)", snapshot.networks.len()),
+    );
+
+    let capabilities = compat::detect();
+    let available = |available: bool| if available { "ok" } else { "unavailable" };
+    report.check("components", available(capabilities.components));
+    report.check("temperature", available(capabilities.temperature));
+    report.check("load average", available(capabilities.load_average));
+    report.check("CPU frequency", available(capabilities.cpu_frequency));
+
+    if snapshot.cpu_contention.is_some() {
+        report.check("steal/iowait", "ok");
+    } else {
+        report.check("steal/iowait", "unavailable (/proc/stat)");
+    }
+
+    report.probe("PSI", "/proc/pressure/cpu", "ok");
+    #[cfg(target_os = "linux")]
+    report.probe("OOM alerts", "/dev/kmsg", "ok");
+
+    #[cfg(feature = "rapl")]
+    report.probe("RAPL", "/sys/class/powercap/intel-rapl:0/energy_uj", "ok");
+    #[cfg(not(feature = "rapl"))]
+    report.check("RAPL", "not built in (feature `rapl`)");
+
+    #[cfg(target_os = "linux")]
+    if Path::new("/var/run/docker.sock").exists() {
+        match std::os::unix::net::UnixStream::connect("/var/run/docker.sock") {
+            Ok(_) => report.check("Docker", "ok"),
+            Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {
+                report.check("Docker", "no permission to connect");
+                report
+                    .missing_permissions
+                    .push("Docker: connect to /var/run/docker.sock".to_string());
+            }
+            Err(err) => report.check("Docker", format!("unavailable ({err})")),
+        }
+    } else {
+        report.check("Docker", "unavailable (no /var/run/docker.sock)");
+    }
+}
@@ -0,0 +1,190 @@
+//! Timestamped log of notable metric changes, shown with `L`
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Local};
+
+use crate::{config::Thresholds, snapshot::SystemSnapshot};
+
+/// Entries beyond this are dropped, oldest first
+const CAPACITY: usize = 200;
+
+/// Memory usage levels in percent that are logged when crossed upwards
+const MEMORY_LEVELS: [f64; 3] = [80.0, 90.0, 95.0];
+
+/// Seconds without any traffic after which an interface counts as down
+const INTERFACE_DOWN_SECS: i64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// The log and what it needs to remember to notice changes between snapshots
+#[derive(Debug, Default)]
+pub struct EventLog {
+    /// oldest first
+    entries: VecDeque<(DateTime<Local>, Severity, String)>,
+    /// cores above the CPU threshold in the last snapshot
+    hot_cores: HashSet<usize>,
+    /// index into [`MEMORY_LEVELS`] of the highest level currently crossed
+    memory_level: Option<usize>,
+    /// `None` until the first snapshot, so existing processes are not logged as new
+    known_pids: Option<HashSet<u32>>,
+    /// watched PIDs that were running in the last snapshot
+    watched_running: HashSet<u32>,
+    /// unix timestamp since which an interface that had traffic before is quiet
+    quiet_since: HashMap<String, i64>,
+    /// interfaces that have had traffic since sys-tui started
+    active_interfaces: HashSet<String>,
+    /// interfaces already logged as down
+    down_interfaces: HashSet<String>,
+}
+
+impl EventLog {
+    pub fn push(&mut self, severity: Severity, message: String) {
+        if self.entries.len() == CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((Local::now(), severity, message));
+    }
+
+    /// newest first
+    pub fn entries(&self) -> impl Iterator<Item = &(DateTime<Local>, Severity, String)> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// logs what changed since the previous snapshot; `watched` are the `[[watch]]` PIDs
+    pub fn observe(&mut self, snapshot: &SystemSnapshot, thresholds: &Thresholds, watched: &[u32]) {
+        self.observe_cpus(snapshot, thresholds);
+        self.observe_memory(snapshot);
+        self.observe_processes(snapshot, watched);
+        self.observe_interfaces(snapshot);
+    }
+
+    fn observe_cpus(&mut self, snapshot: &SystemSnapshot, thresholds: &Thresholds) {
+        let hot: HashSet<usize> = snapshot
+            .cpus
+            .iter()
+            .enumerate()
+            .filter(|(_, cpu)| cpu.usage > thresholds.cpu)
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut heated: Vec<usize> = hot.difference(&self.hot_cores).copied().collect();
+        heated.sort_unstable();
+        for index in heated {
+            let cpu = &snapshot.cpus[index];
+            self.push(
+                Severity::Warning,
+                format!(
+                    "{} above {:.0}% ({:.1}%)",
+                    cpu.name, thresholds.cpu, cpu.usage
+                ),
+            );
+        }
+        self.hot_cores = hot;
+    }
+
+    fn observe_memory(&mut self, snapshot: &SystemSnapshot) {
+        let memory = &snapshot.memory;
+        if memory.total == 0 {
+            return;
+        }
+
+        let used_pct = memory.used as f64 / memory.total as f64 * 100.0;
+        let level = MEMORY_LEVELS.iter().rposition(|level| used_pct >= *level);
+
+        if level > self.memory_level {
+            if let Some(level) = level {
+                let severity = if level == MEMORY_LEVELS.len() - 1 {
+                    Severity::Critical
+                } else {
+                    Severity::Warning
+                };
+                self.push(
+                    severity,
+                    format!(
+                        "memory usage above {:.0}% ({used_pct:.1}%)",
+                        MEMORY_LEVELS[level]
+                    ),
+                );
+            }
+        }
+        self.memory_level = level;
+    }
+
+    fn observe_processes(&mut self, snapshot: &SystemSnapshot, watched: &[u32]) {
+        let pids: HashSet<u32> = snapshot
+            .processes
+            .iter()
+            .map(|process| process.pid)
+            .collect();
+
+        if let Some(known) = &self.known_pids {
+            let started: Vec<String> = snapshot
+                .processes
+                .iter()
+                .filter(|process| !known.contains(&process.pid))
+                .map(|process| format!("process started: {} ({})", process.name, process.pid))
+                .collect();
+            for message in started {
+                self.push(Severity::Info, message);
+            }
+        }
+
+        let running: HashSet<u32> = watched
+            .iter()
+            .copied()
+            .filter(|pid| pids.contains(pid))
+            .collect();
+        let mut exited: Vec<u32> = self.watched_running.difference(&running).copied().collect();
+        exited.sort_unstable();
+        for pid in exited {
+            self.push(Severity::Warning, format!("watched process {pid} exited"));
+        }
+
+        self.watched_running = running;
+        self.known_pids = Some(pids);
+    }
+
+    /// only interfaces that had traffic before can go down, so idle ones stay quiet
+    fn observe_interfaces(&mut self, snapshot: &SystemSnapshot) {
+        for interface in &snapshot.networks {
+            let name = &interface.name;
+
+            if interface.rx_bps + interface.tx_bps > 0.0 {
+                self.active_interfaces.insert(name.clone());
+                self.quiet_since.remove(name);
+                self.down_interfaces.remove(name);
+                continue;
+            }
+            if !self.active_interfaces.contains(name) {
+                continue;
+            }
+
+            let since = *self
+                .quiet_since
+                .entry(name.clone())
+                .or_insert(snapshot.timestamp);
+            if snapshot.timestamp - since >= INTERFACE_DOWN_SECS
+                && self.down_interfaces.insert(name.clone())
+            {
+                self.push(
+                    Severity::Warning,
+                    format!("{name} down: no traffic for {INTERFACE_DOWN_SECS} s"),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! The `Ctrl+F` search across all tabs, which jumps to the tab of the picked result
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::{
+    snapshot::SystemSnapshot,
+    util::{format_bytes, format_bytes_per_sec},
+    Tab,
+};
+
+/// Results shown in the dropdown at most
+const MAX_RESULTS: usize = 10;
+
+/// Where a result leads
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchTarget {
+    /// the tab alone, for widgets and metrics without a row
+    Tab(Tab),
+    /// a core, zoomed into on the CPU tab
+    Core(usize),
+    Interface(String),
+    /// a partition by its mount point
+    Partition(String),
+    Process(u32),
+}
+
+impl SearchTarget {
+    pub fn tab(&self) -> Tab {
+        match self {
+            SearchTarget::Tab(tab) => *tab,
+            SearchTarget::Core(_) => Tab::Cpu,
+            SearchTarget::Interface(_) => Tab::Network,
+            SearchTarget::Partition(_) => Tab::Disk,
+            SearchTarget::Process(_) => Tab::Processes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub tab_name: &'static str,
+    /// the metric and its current value, which is also what the query is matched against
+    pub description: String,
+    pub target: SearchTarget,
+}
+
+/// results whose description contains `query`, ignoring case, in tab order
+pub fn search(query: &str, snapshot: &SystemSnapshot) -> Vec<SearchResult> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return vec![];
+    }
+
+    candidates(snapshot)
+        .into_iter()
+        .filter(|result| result.description.to_lowercase().contains(&query))
+        .take(MAX_RESULTS)
+        .collect()
+}
+
+/// everything the search can find, with the values of `snapshot`
+fn candidates(snapshot: &SystemSnapshot) -> Vec<SearchResult> {
+    let result = |tab_name, description: String, target| SearchResult {
+        tab_name,
+        description,
+        target,
+    };
+    let mut results = vec![];
+
+    results.push(result(
+        "CPU",
+        format!("CPU usage {:.1}%", snapshot.mean_cpu_usage()),
+        SearchTarget::Tab(Tab::Cpu),
+    ));
+    let [one, five, fifteen] = snapshot.load_average;
+    results.push(result(
+        "CPU",
+        format!("Load average {one:.2} {five:.2} {fifteen:.2}"),
+        SearchTarget::Tab(Tab::Cpu),
+    ));
+    if let Some(contention) = snapshot.cpu_contention {
+        results.push(result(
+            "CPU",
+            format!("CPU contention {contention:.1}%"),
+            SearchTarget::Tab(Tab::Cpu),
+        ));
+    }
+    for (core, cpu) in snapshot.cpus.iter().enumerate() {
+        results.push(result(
+            "CPU",
+            format!(
+                "{} usage {:.1}% at {} MHz",
+                cpu.name, cpu.usage, cpu.frequency
+            ),
+            SearchTarget::Core(core),
+        ));
+    }
+
+    let memory = &snapshot.memory;
+    results.push(result(
+        "Memory",
+        format!(
+            "Memory used {} of {}",
+            format_bytes(memory.used),
+            format_bytes(memory.total)
+        ),
+        SearchTarget::Tab(Tab::Memory),
+    ));
+    if let Some(breakdown) = &memory.breakdown {
+        results.push(result(
+            "Memory",
+            format!(
+                "Page cache {}, buffers {}",
+                format_bytes(breakdown.cached),
+                format_bytes(breakdown.buffers)
+            ),
+            SearchTarget::Tab(Tab::Memory),
+        ));
+    }
+    results.push(result(
+        "Memory",
+        format!(
+            "Swap used {} of {}",
+            format_bytes(memory.used_swap),
+            format_bytes(memory.total_swap)
+        ),
+        SearchTarget::Tab(Tab::Memory),
+    ));
+
+    for interface in &snapshot.networks {
+        results.push(result(
+            "Network",
+            format!(
+                "{} ↓{} ↑{}",
+                interface.name,
+                format_bytes_per_sec(interface.rx_bps),
+                format_bytes_per_sec(interface.tx_bps)
+            ),
+            SearchTarget::Interface(interface.name.clone()),
+        ));
+    }
+
+    results.push(result(
+        "Disk",
+        "Total disk I/O".to_string(),
+        SearchTarget::Tab(Tab::Disk),
+    ));
+    for partition in &snapshot.disks {
+        let mount_point = partition.mount_point.to_string_lossy().into_owned();
+        results.push(result(
+            "Disk",
+            format!(
+                "{} on {mount_point} {:.0}% used",
+                partition.name,
+                partition.used_pct()
+            ),
+            SearchTarget::Partition(mount_point),
+        ));
+    }
+
+    results.push(result(
+        "Processes",
+        "Process ages".to_string(),
+        SearchTarget::Tab(Tab::Processes),
+    ));
+    for process in &snapshot.processes {
+        results.push(result(
+            "Processes",
+            format!(
+                "{} (pid {}) {:.1}% CPU, {}",
+                process.name,
+                process.pid,
+                process.cpu_usage,
+                format_bytes(process.memory)
+            ),
+            SearchTarget::Process(process.pid),
+        ));
+    }
+
+    results
+}
+
+/// Outcome of feeding a key to a [`GlobalSearch`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchOutcome {
+    Pending,
+    Jump(SearchTarget),
+    Cancelled,
+}
+
+/// The search bar at the bottom of the screen and its dropdown of results
+#[derive(Debug, Clone, Default)]
+pub struct GlobalSearch {
+    query: String,
+    results: Vec<SearchResult>,
+    selected: usize,
+}
+
+impl GlobalSearch {
+    /// edits the query and moves through the results, searching `snapshot` as the query changes
+    pub fn handle_key(&mut self, code: KeyCode, snapshot: &SystemSnapshot) -> SearchOutcome {
+        match code {
+            KeyCode::Esc => return SearchOutcome::Cancelled,
+            KeyCode::Enter => {
+                return match self.results.get(self.selected) {
+                    Some(result) => SearchOutcome::Jump(result.target.clone()),
+                    None => SearchOutcome::Cancelled,
+                }
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.results.len().saturating_sub(1))
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.update(snapshot);
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.update(snapshot);
+            }
+            _ => {}
+        }
+
+        SearchOutcome::Pending
+    }
+
+    fn update(&mut self, snapshot: &SystemSnapshot) {
+        self.results = search(&self.query, snapshot);
+        self.selected = 0;
+    }
+}
+
+/// draws the search bar over the last row of `area` and the results right above it
+impl Widget for &GlobalSearch {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 {
+            return;
+        }
+
+        let bar = Rect {
+            y: area.bottom() - 1,
+            height: 1,
+            ..area
+        };
+        Clear.render(bar, buf);
+        Paragraph::new(format!("search: {}_", self.query)).render(bar, buf);
+
+        if self.results.is_empty() {
+            return;
+        }
+
+        // the results plus borders, above the bar
+        let height = (self.results.len() as u16 + 2).min(area.height - 1);
+        let dropdown = Rect {
+            y: bar.y - height,
+            height,
+            ..area
+        };
+
+        let block = Block::new()
+            .title_bottom(Line::from("↑/↓ select | Enter go | Esc close").dim())
+            .borders(Borders::ALL)
+            .border_style(Style::new().yellow());
+        let inner = block.inner(dropdown);
+        Clear.render(dropdown, buf);
+        block.render(dropdown, buf);
+
+        let lines: Vec<Line> = self
+            .results
+            .iter()
+            .enumerate()
+            .map(|(index, result)| {
+                let line = Line::from(format!("{:<10} {}", result.tab_name, result.description));
+                if index == self.selected {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -0,0 +1,44 @@
+//! `[[launch]]` entries: commands run in place of the TUI with `Ctrl+<digit>`
+
+use std::{
+    io,
+    path::Path,
+    process::{Command, ExitStatus},
+};
+
+use crate::{config::LaunchConfig, process::drill::in_path};
+
+/// Digits whose Ctrl combination legacy terminals send as another key or not at all
+const AMBIGUOUS_KEYS: &str = "012389";
+
+/// a warning for every entry whose program cannot be found, e.g. because of a typo,
+/// and, without `keyboard_enhancement`, for every key the terminal cannot deliver
+pub fn missing_commands(entries: &[LaunchConfig], keyboard_enhancement: bool) -> Vec<String> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            if !keyboard_enhancement && AMBIGUOUS_KEYS.contains(entry.key) {
+                return Some(format!(
+                    "launch key Ctrl+{} needs a terminal with the kitty keyboard protocol",
+                    entry.key
+                ));
+            }
+
+            let program = entry.command.split_whitespace().next()?;
+            let found = if program.contains('/') {
+                Path::new(program).is_file()
+            } else {
+                in_path(program)
+            };
+
+            (!found).then(|| format!("launch command for Ctrl+{} not found: {program}", entry.key))
+        })
+        .collect()
+}
+
+/// runs `command` with `sh -c` on our terminal and waits for it to exit
+///
+/// The TUI must be suspended around this, the command owns the terminal while it runs.
+pub fn run(command: &str) -> io::Result<ExitStatus> {
+    Command::new("sh").arg("-c").arg(command).status()
+}
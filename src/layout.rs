@@ -0,0 +1,49 @@
+//! Picks how much detail to show from the terminal size
+
+use ratatui::layout::Rect;
+use serde::{Deserialize, Serialize};
+
+/// Terminals lower than this get the compact layout
+const COMPACT_BELOW: u16 = 30;
+/// Terminals lower than this (but not compact) get the medium layout
+const MEDIUM_BELOW: u16 = 45;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    /// per-core bars plus usage history
+    Full,
+    /// per-core bars only
+    Medium,
+    /// a single aggregate CPU bar
+    Compact,
+}
+
+/// Selects a [`LayoutMode`] per frame unless one was forced in the config
+#[derive(Debug, Clone, Copy)]
+pub struct AutoLayout {
+    /// `[layout] mode`, bypassing the size-based selection
+    pub forced: Option<LayoutMode>,
+    /// smallest `(width, height)` the layout is drawn at
+    pub min_size: (u16, u16),
+}
+
+impl AutoLayout {
+    pub fn fits(&self, area: Rect) -> bool {
+        let (min_width, min_height) = self.min_size;
+
+        area.width >= min_width && area.height >= min_height
+    }
+
+    pub fn mode(&self, area: Rect) -> LayoutMode {
+        if let Some(mode) = self.forced {
+            return mode;
+        }
+
+        match area.height {
+            height if height < COMPACT_BELOW => LayoutMode::Compact,
+            height if height < MEDIUM_BELOW => LayoutMode::Medium,
+            _ => LayoutMode::Full,
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! The Dashboard tab's grid of widgets and the `E` mode that rearranges it
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use ratatui::{crossterm::event::KeyCode, layout::Rect};
+use serde::{Deserialize, Serialize};
+
+/// The built-in views that can be placed on the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WidgetKind {
+    Cpu,
+    Memory,
+    Network,
+    Disk,
+    Processes,
+}
+
+impl WidgetKind {
+    pub fn title(self) -> &'static str {
+        match self {
+            WidgetKind::Cpu => "CPU",
+            WidgetKind::Memory => "Memory",
+            WidgetKind::Network => "Network",
+            WidgetKind::Disk => "Disk",
+            WidgetKind::Processes => "Processes",
+        }
+    }
+}
+
+/// A widget spanning `width` cells of one grid row, starting at `column`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Placement {
+    pub widget: WidgetKind,
+    pub row: u16,
+    pub column: u16,
+    pub width: u16,
+}
+
+impl Placement {
+    fn overlaps(&self, other: &Placement) -> bool {
+        self.row == other.row
+            && self.column < other.column + other.width
+            && other.column < self.column + self.width
+    }
+}
+
+/// Widget placements on a `columns` x `rows` grid of equally sized cells
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridLayout {
+    pub columns: u16,
+    pub rows: u16,
+    #[serde(rename = "widget")]
+    pub placements: Vec<Placement>,
+}
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        let place = |widget, row, column, width| Placement {
+            widget,
+            row,
+            column,
+            width,
+        };
+
+        GridLayout {
+            columns: 4,
+            rows: 2,
+            placements: vec![
+                place(WidgetKind::Cpu, 0, 0, 2),
+                place(WidgetKind::Memory, 0, 2, 2),
+                place(WidgetKind::Network, 1, 0, 1),
+                place(WidgetKind::Disk, 1, 1, 1),
+                place(WidgetKind::Processes, 1, 2, 2),
+            ],
+        }
+    }
+}
+
+impl GridLayout {
+    pub fn load(path: &Path) -> io::Result<GridLayout> {
+        let contents = fs::read_to_string(path)?;
+
+        let invalid = |message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {message}", path.display()),
+            )
+        };
+
+        let grid: GridLayout = toml::from_str(&contents).map_err(|err| invalid(err.to_string()))?;
+        grid.validate().map_err(invalid)?;
+
+        Ok(grid)
+    }
+
+    /// writes the layout to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let contents = toml::to_string(self).map_err(io::Error::other)?;
+        fs::write(path, contents)
+    }
+
+    /// checks that every widget lies within the grid and no two widgets overlap
+    fn validate(&self) -> Result<(), String> {
+        if self.columns == 0 || self.rows == 0 {
+            return Err("columns and rows must be at least 1".to_string());
+        }
+
+        for (index, placement) in self.placements.iter().enumerate() {
+            let name = placement.widget.title();
+            if placement.width == 0 {
+                return Err(format!("{name}: width must be at least 1"));
+            }
+            if placement.row >= self.rows || placement.column + placement.width > self.columns {
+                return Err(format!(
+                    "{name}: outside the {}x{} grid",
+                    self.columns, self.rows
+                ));
+            }
+            if let Some(other) = self.placements[..index]
+                .iter()
+                .find(|other| other.overlaps(placement))
+            {
+                return Err(format!("{name}: overlaps {}", other.widget.title()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// the screen area of every placement, in placement order
+    pub fn areas(&self, area: Rect) -> Vec<(WidgetKind, Rect)> {
+        // scale grid lines rather than cell sizes so rounding never leaves gaps
+        let x = |column: u16| {
+            area.x + (u32::from(area.width) * u32::from(column) / u32::from(self.columns)) as u16
+        };
+        let y = |row: u16| {
+            area.y + (u32::from(area.height) * u32::from(row) / u32::from(self.rows)) as u16
+        };
+
+        self.placements
+            .iter()
+            .map(|placement| {
+                let left = x(placement.column);
+                let top = y(placement.row);
+                let rect = Rect {
+                    x: left,
+                    y: top,
+                    width: x(placement.column + placement.width) - left,
+                    height: y(placement.row + 1) - top,
+                };
+
+                (placement.widget, rect)
+            })
+            .collect()
+    }
+}
+
+/// `$XDG_DATA_HOME/sys-tui/layout.toml`, or `~/.local/share/sys-tui/layout.toml`
+pub fn default_path() -> Option<PathBuf> {
+    Some(crate::config::data_dir()?.join("layout.toml"))
+}
+
+/// Outcome of feeding a key to a [`LayoutEditor`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditorResult {
+    Pending,
+    Saved(GridLayout),
+    Cancelled,
+}
+
+/// Edits a copy of a [`GridLayout`], one focused widget at a time
+#[derive(Debug, Clone)]
+pub struct LayoutEditor {
+    grid: GridLayout,
+    /// index into `grid.placements`
+    focused: usize,
+}
+
+impl LayoutEditor {
+    pub fn new(grid: GridLayout) -> Self {
+        LayoutEditor { grid, focused: 0 }
+    }
+
+    /// the layout including all edits so far
+    pub fn grid(&self) -> &GridLayout {
+        &self.grid
+    }
+
+    pub fn focused(&self) -> Option<&Placement> {
+        self.grid.placements.get(self.focused)
+    }
+
+    /// Tab cycles the focus, arrows move, `w`/`s` widen and shrink,
+    /// Enter saves and Escape discards the edits
+    pub fn handle_key(&mut self, code: KeyCode) -> EditorResult {
+        match code {
+            KeyCode::Tab if !self.grid.placements.is_empty() => {
+                self.focused = (self.focused + 1) % self.grid.placements.len()
+            }
+            KeyCode::Left => self.move_focused(0, -1),
+            KeyCode::Right => self.move_focused(0, 1),
+            KeyCode::Up => self.move_focused(-1, 0),
+            KeyCode::Down => self.move_focused(1, 0),
+            KeyCode::Char('w') => self.resize_focused(1),
+            KeyCode::Char('s') => self.resize_focused(-1),
+            KeyCode::Enter => return EditorResult::Saved(self.grid.clone()),
+            KeyCode::Esc => return EditorResult::Cancelled,
+            _ => {}
+        }
+
+        EditorResult::Pending
+    }
+
+    /// moves the focused widget one cell, swapping places with a widget in the way
+    fn move_focused(&mut self, rows: i16, columns: i16) {
+        let Some(&old) = self.focused() else {
+            return;
+        };
+        let (Some(row), Some(column)) = (
+            old.row.checked_add_signed(rows),
+            old.column.checked_add_signed(columns),
+        ) else {
+            return;
+        };
+
+        let moved = Placement { row, column, ..old };
+        let blocking = self
+            .grid
+            .placements
+            .iter()
+            .enumerate()
+            .find(|&(index, other)| index != self.focused && other.overlaps(&moved))
+            .map(|(index, &other)| (index, other));
+
+        let mut changes = vec![(self.focused, moved)];
+        if let Some((index, other)) = blocking {
+            // the other widget takes the old spot, keeping the left-to-right order consistent
+            let (moved_column, other_column) = match columns {
+                1 => (old.column + other.width, old.column),
+                -1 => (other.column, other.column + old.width),
+                _ => (old.column, other.column),
+            };
+            changes = vec![
+                (
+                    self.focused,
+                    Placement {
+                        column: moved_column,
+                        ..moved
+                    },
+                ),
+                (
+                    index,
+                    Placement {
+                        row: old.row,
+                        column: other_column,
+                        ..other
+                    },
+                ),
+            ];
+        }
+
+        self.apply(&changes);
+    }
+
+    fn resize_focused(&mut self, delta: i16) {
+        let Some(&old) = self.focused() else {
+            return;
+        };
+        let Some(width) = old.width.checked_add_signed(delta) else {
+            return;
+        };
+
+        self.apply(&[(self.focused, Placement { width, ..old })]);
+    }
+
+    /// replaces placements by index, unless that would leave an invalid grid
+    fn apply(&mut self, changes: &[(usize, Placement)]) {
+        let mut grid = self.grid.clone();
+        for &(index, placement) in changes {
+            grid.placements[index] = placement;
+        }
+
+        if grid.validate().is_ok() {
+            self.grid = grid;
+        }
+    }
+}
@@ -1,26 +1,104 @@
 use std::{
+    collections::{HashMap, HashSet, VecDeque},
     io,
+    net::IpAddr,
+    path::PathBuf,
+    sync::mpsc::Receiver,
     time::{Duration, Instant},
 };
 
-use chrono::{Local, Utc};
+use clap::Parser;
+
+use chrono::Local;
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Style, Stylize},
-    symbols::border,
-    text::{Line, Span, Text},
-    widgets::{
-        block::{Position, Title},
-        Block, Borders, Paragraph, Widget, Wrap,
-    },
+    text::{Line, Span},
+    widgets::{Block, Borders, Gauge, Paragraph, Row, Table, TableState, Tabs, Widget},
     Frame,
 };
 
-use sysinfo::{Cpu, System};
+use sysinfo::System;
+
+use alert::AlertWebhooks;
+use benchmark::Benchmark;
+use cli::Cli;
+use compat::CapabilitySet;
+use config::{
+    Config, CpuConfig, DiffThreshold, LaunchConfig, ProcessColumn, ProcessesConfig, Thresholds,
+    WatchConfig,
+};
+use cpu_topology::CpuTopology;
+use disk::{DiskIo, DiskStats, MountOption};
+use event_log::{EventLog, Severity};
+use global_search::{GlobalSearch, SearchOutcome, SearchTarget};
+use layout::{AutoLayout, LayoutMode};
+use layout_editor::{EditorResult, GridLayout, LayoutEditor, WidgetKind};
+use net::{details::InterfaceDetails, mtu::MtuWarning};
+use plugin::Plugins;
+use process::ProcessStats;
+use snapshot::{CpuSnapshot, SwapIo, SystemSnapshot};
+use startup_timings::{Phase, StartupTimings};
+use util::{diff_style, format_bytes, format_bytes_per_sec, format_bytes_short, trend_arrow};
+use widgets::{
+    disk_io::TotalDiskIoWidget,
+    env_viewer::EnvViewer,
+    event_log::EventLogView,
+    gradient::{GradientPalette, GradientSparkline},
+    input::{InputResult, NumericInput},
+    memory_bar::MemoryBar,
+    process_age::{AgeBucket, ProcessAgeWidget},
+    self_monitor::{SelfMonitor, WidgetTiming},
+    smaps_viewer::SmapsViewer,
+};
+use worker::{Worker, WorkerOptions};
 
+mod alert;
+mod api;
+mod benchmark;
+mod cli;
+mod compat;
+mod config;
+mod cpu_cache;
+mod cpu_temperature;
+mod cpu_topology;
+mod disk;
+mod dns;
+#[cfg(target_os = "linux")]
+mod docker;
+mod dry_run;
+mod event_log;
+mod global_search;
+mod launch;
+mod layout;
+mod layout_editor;
+mod net;
+mod nvme;
+mod once;
+#[cfg(target_os = "linux")]
+mod oom;
+mod ping;
+mod plugin;
+mod proc_stat;
+mod process;
+#[cfg(target_os = "linux")]
+mod procfs;
+#[cfg(feature = "rapl")]
+mod rapl;
+mod snapshot;
+mod startup_macro;
+mod startup_timings;
+#[cfg(feature = "perf_events")]
+mod syscall_rate;
 mod tui;
+mod util;
+mod watchdog;
+mod web;
+mod widgets;
+mod worker;
+mod ws;
 
 #[derive(Debug)]
 pub struct Clock {}
@@ -37,110 +115,2801 @@ impl Widget for &Clock {
     }
 }
 
+/// Refreshes of swap usage kept for the Memory tab sparkline
+const SWAP_HISTORY_LEN: usize = 60;
+
+/// Refreshes of total disk I/O the Disk tab gauges are scaled over
+const DISK_IO_HISTORY_LEN: usize = 60;
+
+/// Refreshes of mean CPU usage kept for the full layout's sparkline
+const CPU_HISTORY_LEN: usize = 120;
+
+/// Consecutive refreshes without any steal after which the steal band is hidden
+const STEAL_IDLE_SAMPLES: usize = 30;
+
+/// Render durations kept per widget for the Ctrl+S overlay
+const RENDER_TIMING_SAMPLES: usize = 10;
+
+/// Swap page size assumed when converting pages/s to bytes/s
+const SWAP_PAGE_SIZE: f64 = 4096.0;
+
+/// CPU contention in percent above which the header shows a warning on every tab
+const CONTENTION_WARNING_PCT: f32 = 20.0;
+
+/// Refreshes a significant change stays highlighted for
+const DIFF_HIGHLIGHT_REFRESHES: usize = 2;
+
+/// How often interface addresses are re-read, they rarely change
+const ADDRESS_REFRESH: Duration = Duration::from_secs(30);
+
+/// How long the startup timings stay in the status bar
+const STARTUP_STATUS_DURATION: Duration = Duration::from_secs(5);
+
+/// The tabs shown in the header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Cpu,
+    Memory,
+    Network,
+    Disk,
+    Processes,
+    /// several of the above on a grid, arranged with `E`
+    Dashboard,
+    /// a plugin widget, by its index in the plugin registry
+    Plugin(usize),
+}
+
+impl Tab {
+    /// the built-in tabs in display order; plugin tabs follow them
+    const BUILTIN: [Tab; 6] = [
+        Tab::Cpu,
+        Tab::Memory,
+        Tab::Network,
+        Tab::Disk,
+        Tab::Processes,
+        Tab::Dashboard,
+    ];
+}
+
 #[derive(Debug)]
-pub struct App<'a> {
+pub struct App {
     name: String,
     clock: Clock,
-    system: &'a mut System,
+    snapshot: SystemSnapshot,
+    /// the snapshots before `snapshot`, oldest first, kept for `diff_highlight`
+    previous_snapshots: VecDeque<SystemSnapshot>,
+    /// the snapshot before `snapshot`, compared against for trend arrows
+    previous_snapshot: Option<SystemSnapshot>,
+    snapshots: Receiver<SystemSnapshot>,
+    worker: Worker,
+    /// what this host reports, widgets without data show a notice instead
+    capabilities: CapabilitySet,
+    /// the config as loaded, written back with the runtime changes by `Ctrl+W`
+    config: Config,
+    /// `Ctrl+W` asks before replacing an existing config file
+    confirm_config_overwrite: bool,
+    /// per core cache summaries, read once since the topology does not change
+    cpu_caches: Vec<String>,
+    /// `None` where sysfs does not describe it, which hides the physical CPU gauge
+    cpu_topology: Option<CpuTopology>,
+    /// core shown alone on the CPU tab, toggled with Enter
+    cpu_zoom: Option<usize>,
+    /// the per-core grid shows temperatures instead of usage, toggled with `T`
+    cpu_temperatures: bool,
+    /// `--refresh-on-focus`: pause `worker` while the terminal is unfocused
+    refresh_on_focus: bool,
+    /// whether the terminal has focus, as far as focus events tell
+    focused: bool,
+    interface_state: TableState,
+    /// IP addresses by interface, re-read every [`ADDRESS_REFRESH`] like the MTUs
+    interface_addresses: HashMap<String, Vec<IpAddr>>,
+    interface_mtus: HashMap<String, u32>,
+    mtu_warnings: Vec<MtuWarning>,
+    addresses_read_at: Instant,
+    /// details of the selected interface by its name, re-read when the selection
+    /// changes and with the addresses
+    interface_details: Option<(String, Result<InterfaceDetails, String>)>,
+    partition_state: TableState,
+    process_state: TableState,
+    process_config: ProcessesConfig,
+    /// bar of the process age histogram picked with Left / Right
+    age_bucket_selected: usize,
+    /// age bucket the process table is limited to, toggled with Enter
+    age_zoom: Option<AgeBucket>,
+    /// only list processes owned by `current_uid`, toggled with `u`
+    user_only: bool,
+    current_uid: Option<u32>,
+    /// `[[watch]]` entries, see [`Self::send_reload_signal`]
+    watch: Vec<WatchConfig>,
+    /// `[[launch]]` commands by their Ctrl+digit key
+    launch: Vec<LaunchConfig>,
+    /// command of a pressed launch key, run by [`App::run`], which owns the terminal
+    pending_launch: Option<String>,
+    benchmark: Option<Benchmark>,
+    /// read throughput in MB/s by device name
+    benchmark_results: HashMap<String, f64>,
+    status: Option<String>,
+    /// when `status`, if still showing this message, is cleared
+    status_expiry: Option<(Instant, String)>,
+    /// taken after the first frame to report how long startup took
+    startup_timings: Option<StartupTimings>,
+    /// `--alert-webhook` targets and the `[alert]` sound, checked against every snapshot
+    alerts: AlertWebhooks,
+    /// OOM kills reported by the kernel, see [`oom::spawn`]
+    #[cfg(target_os = "linux")]
+    oom_events: Receiver<oom::AlertEvent>,
+    /// banner for the most recent OOM kill, shown until exit
+    oom_banner: Option<String>,
+    tab: Tab,
+    plugins: Plugins,
+    /// bytes of swap used, one entry per refresh, oldest first
+    swap_history: VecDeque<u64>,
+    /// mean CPU usage in percent, one entry per refresh, oldest first
+    cpu_history: VecDeque<u64>,
+    layout: AutoLayout,
+    /// widget placement of the Dashboard tab
+    dashboard: GridLayout,
+    /// where `dashboard` is saved, see [`layout_editor::default_path`]
+    dashboard_path: Option<PathBuf>,
+    /// edits to `dashboard`, open while in layout edit mode
+    layout_editor: Option<LayoutEditor>,
+    /// consecutive refreshes in which no core reported steal
+    steal_idle_samples: usize,
+    /// swap pages in / out per second, same length as `swap_history`
+    swap_io_history: VecDeque<SwapIo>,
+    /// whether any swap traffic was seen since startup
+    swap_io_seen: bool,
+    /// total disk I/O of the listed partitions, one entry per refresh, oldest first
+    disk_io_history: VecDeque<DiskIo>,
+    thresholds: Thresholds,
+    cpu_config: CpuConfig,
+    gradient: GradientPalette,
+    /// `[title_bar] format`, the window title while `title_active`
+    title_format: String,
+    /// the window title shows live metrics, toggled with `n`
+    title_active: bool,
+    /// core used by `P`, from `[performance] pin_to_core`
+    pin_to_core: usize,
+    diff_threshold: Option<DiffThreshold>,
+    threshold_input: Option<NumericInput>,
+    /// environment popup of the selected process, opened with `v`
+    env_viewer: Option<EnvViewer>,
+    /// the search across all tabs, opened with `Ctrl+F`
+    global_search: Option<GlobalSearch>,
+    /// memory map popup of the selected process, opened with `m`
+    smaps_viewer: Option<SmapsViewer>,
+    event_log: EventLog,
+    /// the event log popup, opened with `L` since `E` is taken by the layout editor
+    event_log_view: Option<EventLogView>,
+    /// case-insensitive substring filter applied to the network and disk tables
+    filter: String,
+    editing_filter: bool,
+    fullscreen: bool,
+    paused: bool,
+    /// whether the Ctrl+S self-monitoring overlay is shown
+    self_monitor: bool,
+    started: Instant,
+    frames: u64,
+    /// total time spent in `terminal.draw`
+    render_time: Duration,
+    /// the last render durations of each widget, only recorded while `self_monitor` is on
+    render_timings: HashMap<&'static str, VecDeque<Duration>>,
+    /// keys synthesized from `startup_macro`, replayed one per frame
+    pending_keys: VecDeque<KeyCode>,
     exit: bool,
 }
 
-impl App<'_> {
+impl App {
+    /// sets up everything the TUI shows, starting the refresh worker from `config`
+    ///
+    /// `keyboard_enhancement` tells whether the terminal can deliver every Ctrl+digit
+    fn new(
+        cli: &Cli,
+        config: Config,
+        keyboard_enhancement: bool,
+        timings: &mut StartupTimings,
+    ) -> io::Result<App> {
+        let (startup_keys, mut startup_warnings) =
+            startup_macro::parse(config.startup_macro.as_deref().unwrap_or_default());
+        startup_warnings.extend(launch::missing_commands(
+            &config.launch,
+            keyboard_enhancement,
+        ));
+
+        let ping = cli.ping.clone().map(ping::spawn);
+        let dns = cli.dns_check.iter().cloned().map(dns::spawn).collect();
+        let capabilities = compat::detect();
+        let worker = Worker::spawn(
+            WorkerOptions {
+                process_interval: Duration::from_secs_f64(config.processes.refresh_interval_secs),
+                ping,
+                dns,
+                temperatures: capabilities.temperature,
+                ..Default::default()
+            },
+            timings,
+        );
+        let snapshot = worker
+            .snapshot
+            .lock()
+            .map_err(|_| io::Error::other("refresh worker panicked"))?
+            .clone();
+
+        let plugins = match &cli.plugin_dir {
+            Some(dir) => {
+                let (plugins, errors) = Plugins::load_dir(dir)?;
+                startup_warnings.extend(errors.into_iter().map(|err| format!("plugin {err}")));
+                plugins
+            }
+            None => Plugins::none(),
+        };
+
+        let dashboard_path = layout_editor::default_path();
+        let dashboard = match &dashboard_path {
+            Some(path) if path.exists() => GridLayout::load(path).unwrap_or_else(|err| {
+                startup_warnings.push(format!("layout {err}"));
+                GridLayout::default()
+            }),
+            _ => GridLayout::default(),
+        };
+
+        let mut app = App::with_snapshot(cli, config, worker, snapshot);
+        app.name = System::host_name().expect("Could not get name of host.");
+        app.capabilities = capabilities;
+        app.cpu_caches = cpu_cache::read_all()
+            .iter()
+            .map(|levels| cpu_cache::summary(levels))
+            .collect();
+        app.cpu_topology = CpuTopology::detect();
+        app.current_uid = process::current_uid();
+        #[cfg(target_os = "linux")]
+        {
+            app.oom_events = oom::spawn();
+        }
+        app.plugins = plugins;
+        app.dashboard = dashboard;
+        app.dashboard_path = dashboard_path;
+        app.status = (!startup_warnings.is_empty()).then(|| startup_warnings.join("; "));
+        app.pending_keys = startup_keys.into();
+
+        Ok(app)
+    }
+
+    /// an app showing `snapshot` that knows nothing about the host yet, which
+    /// [`App::new`] fills in; tests draw it as it is
+    fn with_snapshot(cli: &Cli, config: Config, worker: Worker, snapshot: SystemSnapshot) -> App {
+        let snapshots = worker.subscribe();
+
+        App {
+            clock: Clock {},
+            name: snapshot.hostname.clone(),
+            snapshot,
+            previous_snapshots: VecDeque::new(),
+            previous_snapshot: None,
+            snapshots,
+            worker,
+            capabilities: CapabilitySet::default(),
+            config: config.clone(),
+            confirm_config_overwrite: false,
+            cpu_caches: vec![],
+            cpu_zoom: None,
+            cpu_temperatures: false,
+            cpu_topology: None,
+            refresh_on_focus: cli.refresh_on_focus,
+            focused: true,
+            interface_state: TableState::default(),
+            interface_addresses: HashMap::new(),
+            interface_mtus: HashMap::new(),
+            mtu_warnings: vec![],
+            addresses_read_at: Instant::now(),
+            interface_details: None,
+            partition_state: TableState::default(),
+            process_state: TableState::default(),
+            process_config: config.processes,
+            age_bucket_selected: 0,
+            age_zoom: None,
+            user_only: false,
+            current_uid: None,
+            watch: config.watch,
+            launch: config.launch,
+            pending_launch: None,
+            benchmark: None,
+            benchmark_results: HashMap::new(),
+            status: None,
+            status_expiry: None,
+            startup_timings: None,
+            alerts: AlertWebhooks::new(cli.alert_webhook.clone(), config.alert.sound_file),
+            #[cfg(target_os = "linux")]
+            oom_events: std::sync::mpsc::channel().1,
+            oom_banner: None,
+            tab: Tab::Cpu,
+            plugins: Plugins::none(),
+            swap_history: VecDeque::with_capacity(SWAP_HISTORY_LEN),
+            disk_io_history: VecDeque::with_capacity(DISK_IO_HISTORY_LEN),
+            cpu_history: VecDeque::with_capacity(CPU_HISTORY_LEN),
+            steal_idle_samples: 0,
+            layout: AutoLayout {
+                forced: config.layout.mode,
+                min_size: (config.layout.min_width, config.layout.min_height),
+            },
+            dashboard: GridLayout::default(),
+            dashboard_path: None,
+            layout_editor: None,
+            swap_io_history: VecDeque::with_capacity(SWAP_HISTORY_LEN),
+            swap_io_seen: false,
+            thresholds: config.thresholds,
+            cpu_config: config.cpu,
+            gradient: config.gradient,
+            title_format: config.title_bar.format,
+            title_active: false,
+            pin_to_core: config.performance.pin_to_core,
+            diff_threshold: config.diff_highlight.then_some(config.diff_threshold),
+            threshold_input: None,
+            env_viewer: None,
+            global_search: None,
+            smaps_viewer: None,
+            event_log: EventLog::default(),
+            event_log_view: None,
+            filter: String::new(),
+            editing_filter: false,
+            fullscreen: false,
+            paused: false,
+            self_monitor: false,
+            started: Instant::now(),
+            frames: 0,
+            render_time: Duration::ZERO,
+            render_timings: HashMap::new(),
+            pending_keys: VecDeque::new(),
+            exit: false,
+        }
+    }
+
     /// runs the application's main loop until the user quits
     pub fn run(&mut self, terminal: &mut tui::Tui, tick_rate: Duration) -> io::Result<()> {
-        let last_tick = Instant::now();
+        let mut last_tick = Instant::now();
 
         loop {
             if self.exit {
                 return Ok(());
             }
 
+            if !self.paused {
+                self.receive_snapshots();
+            }
+            self.poll_benchmark();
+            self.update_interface_details();
+            #[cfg(target_os = "linux")]
+            for event in self.oom_events.try_iter() {
+                self.event_log.push(Severity::Critical, event.banner());
+                self.oom_banner = Some(event.banner());
+            }
+
+            let render_start = Instant::now();
             terminal.draw(|frame| self.render_frame(frame))?;
+            self.frames += 1;
+            self.render_time += render_start.elapsed();
+
+            if let Some(mut timings) = self.startup_timings.take() {
+                timings.mark(Phase::FirstFrame);
+                if self.status.is_none() {
+                    self.show_status_for(timings.summary(), STARTUP_STATUS_DURATION);
+                }
+            }
+            if let Some((until, message)) = &self.status_expiry {
+                if *until <= Instant::now() {
+                    if self.status.as_ref() == Some(message) {
+                        self.status = None;
+                    }
+                    self.status_expiry = None;
+                }
+            }
+
+            if let Some(command) = self.pending_launch.take() {
+                self.launch(terminal, &command)?;
+            }
+
+            if let Some(code) = self.pending_keys.pop_front() {
+                self.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+            }
 
             let timeout = tick_rate
                 .checked_sub(last_tick.elapsed())
                 .unwrap_or(Duration::ZERO);
 
             if event::poll(timeout)? {
-                if let Event::Key(key) = event::read()? {
-                    if let KeyCode::Char('q') = key.code {
-                        self.exit = true;
+                match event::read()? {
+                    Event::Key(key) => self.handle_key_event(key),
+                    Event::FocusGained => self.set_focused(true),
+                    Event::FocusLost => self.set_focused(false),
+                    _ => {}
+                }
+            }
+
+            if last_tick.elapsed() >= tick_rate {
+                last_tick = Instant::now();
+            }
+        }
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        if !self.refresh_on_focus {
+            return;
+        }
+
+        self.focused = focused;
+        self.worker.set_paused(!focused);
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+
+        if let Some(input) = &mut self.threshold_input {
+            match input.handle_key(key.code) {
+                InputResult::Accepted(value) => {
+                    self.set_threshold(value);
+                    self.threshold_input = None;
+                }
+                InputResult::Cancelled => self.threshold_input = None,
+                InputResult::Pending => {}
+            }
+            return;
+        }
+
+        if let Some(editor) = &mut self.layout_editor {
+            match editor.handle_key(key.code) {
+                EditorResult::Saved(grid) => {
+                    self.save_dashboard(grid);
+                    self.layout_editor = None;
+                }
+                EditorResult::Cancelled => self.layout_editor = None,
+                EditorResult::Pending => {}
+            }
+            return;
+        }
+
+        if self.confirm_config_overwrite {
+            self.confirm_config_overwrite = false;
+            match key.code {
+                KeyCode::Char('y') => self.write_config(),
+                _ => self.status = Some("config not saved".to_string()),
+            }
+            return;
+        }
+
+        if let Some(search) = &mut self.global_search {
+            match search.handle_key(key.code, &self.snapshot) {
+                SearchOutcome::Jump(target) => {
+                    self.jump_to(target);
+                    self.global_search = None;
+                }
+                SearchOutcome::Cancelled => self.global_search = None,
+                SearchOutcome::Pending => {}
+            }
+            return;
+        }
+
+        if let Some(viewer) = &mut self.env_viewer {
+            if !viewer.handle_key(key.code) {
+                self.env_viewer = None;
+            }
+            return;
+        }
+
+        if let Some(viewer) = &mut self.smaps_viewer {
+            if !viewer.handle_key(key.code) {
+                self.smaps_viewer = None;
+            }
+            return;
+        }
+
+        if let Some(view) = &mut self.event_log_view {
+            if !view.handle_key(key.code, self.event_log.len()) {
+                self.event_log_view = None;
+            }
+            return;
+        }
+
+        if self.editing_filter {
+            match key.code {
+                KeyCode::Char(c) => self.filter.push(c),
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Enter => self.editing_filter = false,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.editing_filter = false;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char(c)
+                if key.modifiers.contains(KeyModifiers::CONTROL) && c.is_ascii_digit() =>
+            {
+                match self.launch.iter().find(|launch| launch.key == c) {
+                    Some(launch) => self.pending_launch = Some(launch.command.clone()),
+                    None => self.status = Some(format!("no [[launch]] command for Ctrl+{c}")),
+                }
+            }
+            // Shift is part of the character, any other modifier means a different binding
+            KeyCode::Char(c)
+                if (key.modifiers - KeyModifiers::SHIFT).is_empty()
+                    && self.tab == Tab::Processes
+                    && self.watch_entry(c).is_some() =>
+            {
+                self.send_reload_signal(c)
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.self_monitor = !self.self_monitor
+            }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.save_config()
+            }
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.global_search = Some(GlobalSearch::default())
+            }
+            KeyCode::Char('q') => self.exit(),
+            KeyCode::Char('f') => self.fullscreen = !self.fullscreen,
+            KeyCode::Char('p') => self.paused = !self.paused,
+            KeyCode::Char('P') => self.pin_to_core(),
+            KeyCode::Char('L') => self.event_log_view = Some(EventLogView::default()),
+            KeyCode::Char('n') => self.toggle_title(),
+            KeyCode::Char('E') => {
+                self.tab = Tab::Dashboard;
+                self.layout_editor = Some(LayoutEditor::new(self.dashboard.clone()));
+            }
+            KeyCode::Char('/') => {
+                self.filter.clear();
+                self.editing_filter = true;
+            }
+            KeyCode::Esc => self.filter.clear(),
+            KeyCode::Char('a') => self.threshold_input = self.threshold_input_for_tab(),
+            KeyCode::Tab => self.tab = self.next_tab(),
+            KeyCode::Char(c @ '1'..='9') => {
+                if let Some(tab) = self.tabs().get(c as usize - '1' as usize) {
+                    self.tab = *tab;
+                }
+            }
+            KeyCode::Char('B') if self.tab == Tab::Disk => self.start_benchmark(),
+            KeyCode::Char('v') if self.tab == Tab::Processes => self.open_env_viewer(),
+            KeyCode::Char('m') if self.tab == Tab::Processes => self.open_smaps_viewer(),
+            KeyCode::Char('o') if self.tab == Tab::Processes => self.open_in_terminal(),
+            KeyCode::Char('K') if self.tab == Tab::Processes => {
+                self.process_config.hide_kernel_threads = !self.process_config.hide_kernel_threads
+            }
+            KeyCode::Char('u') if self.tab == Tab::Processes => {
+                if self.current_uid.is_some() {
+                    self.user_only = !self.user_only;
+                    self.process_state.select(Some(0));
+                } else {
+                    self.status = Some("user filter is not supported on this platform".to_string());
+                }
+            }
+            KeyCode::Char('T') if self.tab == Tab::Cpu => {
+                self.cpu_temperatures = !self.cpu_temperatures
+            }
+            KeyCode::Enter if self.tab == Tab::Cpu => {
+                self.cpu_zoom = match self.cpu_zoom {
+                    Some(_) => None,
+                    None => Some(0),
+                }
+            }
+            KeyCode::Left | KeyCode::Right if self.tab == Tab::Cpu => {
+                if let Some(core) = self.cpu_zoom {
+                    let cores = self.snapshot.cpus.len().max(1);
+                    self.cpu_zoom = Some(if key.code == KeyCode::Left {
+                        (core + cores - 1) % cores
+                    } else {
+                        (core + 1) % cores
+                    });
+                }
+            }
+            KeyCode::Left if self.tab == Tab::Processes => {
+                self.age_bucket_selected = self.age_bucket_selected.saturating_sub(1)
+            }
+            KeyCode::Right if self.tab == Tab::Processes => {
+                self.age_bucket_selected =
+                    (self.age_bucket_selected + 1).min(AgeBucket::ALL.len() - 1)
+            }
+            KeyCode::Enter if self.tab == Tab::Processes => {
+                let bucket = AgeBucket::ALL[self.age_bucket_selected];
+                self.age_zoom = (self.age_zoom != Some(bucket)).then_some(bucket);
+                self.process_state.select(Some(0));
+            }
+            KeyCode::Up | KeyCode::Down => {
+                let offset = if key.code == KeyCode::Up { -1 } else { 1 };
+
+                match self.tab {
+                    Tab::Network => {
+                        let len = self
+                            .snapshot
+                            .networks
+                            .iter()
+                            .filter(|interface| matches_filter(&self.filter, &interface.name))
+                            .count();
+                        select_row(&mut self.interface_state, len, offset)
                     }
+                    Tab::Disk => {
+                        let len = self
+                            .snapshot
+                            .disks
+                            .iter()
+                            .filter(|partition| partition_matches(&self.filter, partition))
+                            .count();
+                        select_row(&mut self.partition_state, len, offset)
+                    }
+                    Tab::Processes => {
+                        let len = self.listed_processes().count();
+                        select_row(&mut self.process_state, len, offset)
+                    }
+                    Tab::Cpu | Tab::Memory | Tab::Dashboard | Tab::Plugin(_) => {}
                 }
             }
+            _ => {}
         }
     }
 
-    fn render_frame(&mut self, frame: &mut Frame) {
-        let mut cols: Vec<Constraint> = vec![];
+    /// one-character `trend_arrow` of a value since the previous refresh, `–` if it is new
+    fn trend(&self, value: impl Fn(&SystemSnapshot) -> Option<f64>) -> Span<'static> {
+        let old = self.previous_snapshot.as_ref().and_then(&value);
+
+        match (old, value(&self.snapshot)) {
+            (Some(old), Some(new)) => trend_arrow(old, new),
+            _ => trend_arrow(0.0, 0.0),
+        }
+    }
+
+    /// `diff_style` of a value over the last refreshes, `None` values are never highlighted
+    ///
+    /// `relative` values use `diff_threshold.relative`, but never less than
+    /// `diff_threshold.absolute`; everything else is a percentage compared
+    /// against `diff_threshold.points`
+    fn diff_style(&self, value: impl Fn(&SystemSnapshot) -> Option<f32>, relative: bool) -> Style {
+        let Some(threshold) = self.diff_threshold else {
+            return Style::default();
+        };
+
+        let values: Vec<Option<f32>> = self
+            .previous_snapshots
+            .iter()
+            .chain([&self.snapshot])
+            .map(value)
+            .collect();
+
+        values
+            .windows(2)
+            .filter_map(|pair| Some((pair[0]?, pair[1]?)))
+            .fold(Style::default(), |style, (old, new)| {
+                let threshold = if relative {
+                    (old.abs() * threshold.relative / 100.0).max(threshold.absolute)
+                } else {
+                    threshold.points
+                };
+
+                style.patch(diff_style(old, new, threshold))
+            })
+    }
+
+    /// built-in tabs followed by one tab per plugin widget
+    fn tabs(&self) -> Vec<Tab> {
+        let plugins = (0..self.plugins.widgets().len()).map(Tab::Plugin);
 
-        std::thread::sleep(Duration::from_secs(1));
+        Tab::BUILTIN.into_iter().chain(plugins).collect()
+    }
+
+    fn next_tab(&self) -> Tab {
+        let tabs = self.tabs();
+        let index = tabs.iter().position(|tab| *tab == self.tab).unwrap_or(0);
 
-        self.system.refresh_cpu_all();
+        tabs[(index + 1) % tabs.len()]
+    }
 
-        for _cpu in self.system.cpus() {
-            let col_size: usize = 100 / self.system.cpus().len();
-            cols.push(Constraint::Percentage(col_size.try_into().unwrap()));
+    fn tab_title(&self, tab: Tab) -> &str {
+        match tab {
+            Tab::Cpu => "CPU",
+            Tab::Memory => "Memory",
+            Tab::Network => "Network",
+            Tab::Disk => "Disk",
+            Tab::Processes => "Processes",
+            Tab::Dashboard => "Dashboard",
+            Tab::Plugin(index) => self.plugins.widgets()[index].name(),
         }
+    }
 
-        let outer_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(vec![Constraint::Percentage(5), Constraint::Percentage(95)])
-            .split(frame.size());
+    /// the tab bar label: a short live metric for the builtin metric tabs, at most 10 characters
+    fn tab_label(&self, tab: Tab) -> String {
+        let snapshot = &self.snapshot;
 
-        let inner_layout = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(cols)
-            .split(outer_layout[1]);
+        match tab {
+            Tab::Cpu => format!("CPU {:.0}%", snapshot.mean_cpu_usage()),
+            Tab::Memory => format!("MEM {}", format_bytes_short(snapshot.memory.used)),
+            Tab::Disk => match snapshot
+                .disks
+                .iter()
+                .map(|partition| partition.used_pct())
+                .max_by(f64::total_cmp)
+            {
+                Some(fullest) => format!("DSK {fullest:.0}%"),
+                None => "DSK".to_string(),
+            },
+            Tab::Network => {
+                let inbound: f64 = snapshot
+                    .networks
+                    .iter()
+                    .map(|interface| interface.rx_bps)
+                    .sum();
+                format!("NET {}↓", format_bytes_short(inbound as u64))
+            }
+            _ => self.tab_title(tab).to_string(),
+        }
+    }
+
+    /// builds the threshold input for the widget on the active tab
+    fn threshold_input_for_tab(&self) -> Option<NumericInput> {
+        let input = match self.tab {
+            Tab::Cpu => NumericInput::new(
+                "CPU alert threshold (%)",
+                self.thresholds.cpu.into(),
+                0.0,
+                100.0,
+            ),
+            Tab::Memory => NumericInput::new(
+                "Memory alert threshold (% used)",
+                self.thresholds.memory,
+                0.0,
+                100.0,
+            ),
+            Tab::Network => NumericInput::new(
+                "Network alert threshold (MiB/s)",
+                self.thresholds.network,
+                0.0,
+                100_000.0,
+            ),
+            Tab::Disk => NumericInput::new(
+                "Disk alert threshold (% used)",
+                self.thresholds.disk,
+                0.0,
+                100.0,
+            ),
+            Tab::Processes | Tab::Dashboard | Tab::Plugin(_) => return None,
+        };
 
-        self.render_clock(frame, outer_layout[0]);
+        Some(input)
+    }
 
-        for (index, cpu) in self.system.cpus().iter().enumerate() {
-            self.render_cpu(frame, cpu, inner_layout[index]);
+    fn set_threshold(&mut self, value: f64) {
+        match self.tab {
+            Tab::Cpu => self.thresholds.cpu = value as f32,
+            Tab::Memory => self.thresholds.memory = value,
+            Tab::Network => self.thresholds.network = value,
+            Tab::Disk => self.thresholds.disk = value,
+            Tab::Processes | Tab::Dashboard | Tab::Plugin(_) => {}
         }
     }
 
-    fn render_cpu(&self, frame: &mut Frame, cpu: &Cpu, area: Rect) {
-        let cpu_block = Block::new().title(cpu.name()).borders(Borders::ALL);
-        let cpu_widget = Paragraph::new(cpu.cpu_usage().to_string()).block(cpu_block);
+    /// the process highlighted in the Processes tab
+    fn selected_process(&self) -> Option<&ProcessStats> {
+        let index = self.process_state.selected()?;
 
-        frame.render_widget(cpu_widget, area)
+        self.listed_processes().nth(index)
     }
 
-    fn render_clock(&self, frame: &mut Frame, area: Rect) {
-        let tz = Local::now().naive_local();
+    /// the `[[watch]]` entry of the selected process bound to `key`
+    fn watch_entry(&self, key: char) -> Option<WatchConfig> {
+        let pid = self.selected_process()?.pid;
 
-        let time_str = tz.format("%H:%M:%S").to_string();
+        self.watch
+            .iter()
+            .find(|watch| watch.pid == pid && watch.reload_key == key)
+            .copied()
+    }
 
-        let time = Paragraph::new(time_str);
+    fn send_reload_signal(&mut self, key: char) {
+        let Some(watch) = self.watch_entry(key) else {
+            return;
+        };
+        let signal = watch.reload_signal.name();
 
-        frame.render_widget(time, area)
+        self.status = Some(match process::send_signal(watch.pid, watch.reload_signal) {
+            Ok(()) => format!("sent {signal} to {}", watch.pid),
+            Err(err) => format!("sending {signal} to {} failed: {err}", watch.pid),
+        });
     }
 
-    fn exit(&mut self) {
-        self.exit = true;
+    /// suspends the TUI while `command` runs, then refreshes everything it missed
+    fn launch(&mut self, terminal: &mut tui::Tui, command: &str) -> io::Result<()> {
+        tui::restore()?;
+        let result = launch::run(command);
+        tui::resume(terminal)?;
+
+        if self.title_active {
+            self.title_active = false;
+            self.toggle_title();
+        }
+        self.worker.refresh_now();
+
+        match result {
+            Ok(status) if status.success() => {}
+            Ok(status) => self.status = Some(format!("`{command}` exited with {status}")),
+            Err(err) => self.status = Some(format!("running `{command}` failed: {err}")),
+        }
+        Ok(())
     }
-}
 
-fn to_gigabytes(bytes: u64) -> f32 {
-    ((bytes as f32 / 1024.0) / 1024.0) / 1024.0
-}
+    /// the loaded config with the thresholds and process options changed at runtime
+    fn effective_config(&self) -> Config {
+        Config {
+            thresholds: self.thresholds,
+            processes: self.process_config.clone(),
+            ..self.config.clone()
+        }
+    }
 
-fn main() -> io::Result<()> {
-    let mut sys = System::new_all();
-    sys.refresh_all();
+    /// writes [`App::effective_config`] to the default config path, asking first if it exists
+    fn save_config(&mut self) {
+        match config::default_path() {
+            Some(path) if path.exists() => self.confirm_config_overwrite = true,
+            Some(_) => self.write_config(),
+            None => self.status = Some("no config directory, set $HOME".to_string()),
+        }
+    }
 
-    let tick_rate = Duration::from_millis(250);
+    fn write_config(&mut self) {
+        let Some(path) = config::default_path() else {
+            return;
+        };
+
+        self.status = Some(match self.effective_config().save(&path) {
+            Ok(()) => format!("config written to {}", path.display()),
+            Err(err) => format!("writing {} failed: {err}", path.display()),
+        });
+    }
 
-    let mut app = App {
-        clock: Clock {},
-        name: System::host_name().expect("Could not get name of host."),
-        system: &mut sys,
-        exit: false,
-    };
+    /// switches to the tab of a global search result and selects its row
+    fn jump_to(&mut self, target: SearchTarget) {
+        self.tab = target.tab();
+        self.filter.clear();
 
-    let mut terminal = tui::init()?;
-    let app_result = app.run(&mut terminal, tick_rate);
-    tui::restore()?;
-    app_result
+        match target {
+            SearchTarget::Tab(_) => {}
+            SearchTarget::Core(core) => self.cpu_zoom = Some(core),
+            SearchTarget::Interface(name) => {
+                let index = self
+                    .snapshot
+                    .networks
+                    .iter()
+                    .position(|interface| interface.name == name);
+                self.interface_state.select(index);
+            }
+            SearchTarget::Partition(mount_point) => {
+                let index =
+                    self.snapshot.disks.iter().position(|partition| {
+                        partition.mount_point.to_string_lossy() == mount_point
+                    });
+                self.partition_state.select(index);
+            }
+            SearchTarget::Process(pid) => {
+                self.age_zoom = None;
+                let index = self
+                    .listed_processes()
+                    .position(|process| process.pid == pid);
+                match index {
+                    Some(index) => self.process_state.select(Some(index)),
+                    None => self.status = Some(format!("process {pid} is hidden or has exited")),
+                }
+            }
+        }
+    }
+
+    fn open_env_viewer(&mut self) {
+        let Some(process) = self.selected_process() else {
+            return;
+        };
+
+        let title = format!("Environment of {} ({})", process.name, process.pid);
+        self.env_viewer = Some(match process::read_environ(process.pid) {
+            Ok(vars) => EnvViewer::new(title, vars, None),
+            Err(message) => EnvViewer::new(title, vec![], Some(message)),
+        });
+    }
+
+    fn open_smaps_viewer(&mut self) {
+        let Some(process) = self.selected_process() else {
+            return;
+        };
+
+        let title = format!("Memory map of {} ({})", process.name, process.pid);
+        let summary =
+            process::smaps::read_smaps_summary(process.pid).map_err(|err| match err.kind() {
+                io::ErrorKind::PermissionDenied => "requires same UID or root".to_string(),
+                io::ErrorKind::NotFound => "process exited".to_string(),
+                _ => format!("reading smaps failed: {err}"),
+            });
+        self.smaps_viewer = Some(SmapsViewer::new(title, summary));
+    }
+
+    /// opens top / htop for the selected process in a new terminal window
+    fn open_in_terminal(&mut self) {
+        let Some(process) = self.selected_process() else {
+            return;
+        };
+        let command = process::drill::command(process.pid);
+        let command_line = command.join(" ");
+
+        let terminal = process::drill::terminal(self.process_config.drill_terminal.as_deref());
+        self.status = Some(match terminal {
+            Some(terminal) => match process::drill::spawn(&terminal, &command) {
+                Ok(()) => format!("opened `{command_line}` in {terminal}"),
+                Err(err) => {
+                    format!("starting {terminal} failed ({err}), run `{command_line}` manually")
+                }
+            },
+            None => format!(
+                "no terminal found: set $TERMINAL or [processes] drill_terminal, or run `{command_line}` manually"
+            ),
+        });
+    }
+
+    fn save_dashboard(&mut self, grid: GridLayout) {
+        self.status = Some(match &self.dashboard_path {
+            Some(path) => match grid.save(path) {
+                Ok(()) => format!("layout saved to {}", path.display()),
+                Err(err) => format!("saving layout to {} failed: {err}", path.display()),
+            },
+            None => "layout not saved: neither XDG_DATA_HOME nor HOME is set".to_string(),
+        });
+        self.dashboard = grid;
+    }
+
+    fn pin_to_core(&mut self) {
+        let core = self.pin_to_core;
+
+        self.status = Some(match process::pin_self_to_core(core) {
+            Ok(()) => format!("pinned sys-tui to core {core}"),
+            Err(err) => format!("pinning to core {core} failed: {err}"),
+        });
+    }
+
+    /// benchmarks the selected partition unless a benchmark is already running
+    fn start_benchmark(&mut self) {
+        if self.benchmark.is_some() {
+            return;
+        }
+
+        let Some(partition) = self.partition_state.selected().and_then(|index| {
+            self.snapshot
+                .disks
+                .iter()
+                .filter(|partition| partition_matches(&self.filter, partition))
+                .nth(index)
+        }) else {
+            return;
+        };
+
+        self.status = None;
+        self.benchmark = Some(Benchmark::start(&partition.name, &partition.mount_point));
+    }
+
+    /// re-reads addresses and MTUs, logging MTU warnings that are new
+    fn refresh_addresses(&mut self) {
+        self.interface_addresses = net::addresses::read_interface_addresses();
+        self.interface_mtus = net::mtu::read_mtus(
+            self.snapshot
+                .networks
+                .iter()
+                .map(|interface| interface.name.as_str()),
+        );
+        self.addresses_read_at = Instant::now();
+        self.interface_details = None;
+
+        // only interfaces with a routable address take part, which leaves out loopback
+        let active: HashMap<String, u32> = self
+            .interface_mtus
+            .iter()
+            .filter(|(name, _)| {
+                self.interface_addresses
+                    .get(*name)
+                    .is_some_and(|addresses| {
+                        net::addresses::primary_address(addresses)
+                            .is_some_and(|address| !address.is_loopback())
+                    })
+            })
+            .map(|(name, mtu)| (name.clone(), *mtu))
+            .collect();
+        let gateway = net::mtu::default_route_interface();
+        let warnings = net::mtu::mtu_warnings(&active, gateway.as_deref());
+
+        for warning in &warnings {
+            if !self.mtu_warnings.contains(warning) {
+                self.event_log
+                    .push(Severity::Warning, warning.message.clone());
+            }
+        }
+        self.mtu_warnings = warnings;
+    }
+
+    /// the interface selected in the network table
+    fn selected_interface(&self) -> Option<&net::InterfaceStats> {
+        self.interface_state.selected().and_then(|index| {
+            self.snapshot
+                .networks
+                .iter()
+                .filter(|interface| matches_filter(&self.filter, &interface.name))
+                .nth(index)
+        })
+    }
+
+    /// reads the details of the selected interface if they are not cached yet
+    fn update_interface_details(&mut self) {
+        let Some(name) = self
+            .selected_interface()
+            .map(|interface| interface.name.clone())
+        else {
+            return;
+        };
+        if matches!(&self.interface_details, Some((cached, _)) if *cached == name) {
+            return;
+        }
+
+        let details = InterfaceDetails::read(&name).map_err(|err| err.to_string());
+        self.interface_details = Some((name, details));
+    }
+
+    /// takes the most recent snapshot published by the worker, if any
+    fn receive_snapshots(&mut self) {
+        let Some(snapshot) = self.snapshots.try_iter().last() else {
+            return;
+        };
+
+        let previous = std::mem::replace(&mut self.snapshot, snapshot);
+        if self.addresses_read_at.elapsed() >= ADDRESS_REFRESH {
+            self.refresh_addresses();
+        }
+        if self.diff_threshold.is_some() {
+            if self.previous_snapshots.len() == DIFF_HIGHLIGHT_REFRESHES {
+                self.previous_snapshots.pop_front();
+            }
+            self.previous_snapshots.push_back(previous.clone());
+        }
+        self.previous_snapshot = Some(previous);
+
+        let watched: Vec<u32> = self.watch.iter().map(|watch| watch.pid).collect();
+        self.event_log
+            .observe(&self.snapshot, &self.thresholds, &watched);
+        self.alerts.check(&self.snapshot, &self.thresholds);
+        if self.title_active {
+            let _ = self.update_title();
+        }
+        self.plugins.refresh();
+
+        if self.swap_history.len() == SWAP_HISTORY_LEN {
+            self.swap_history.pop_front();
+        }
+        self.swap_history.push_back(self.snapshot.memory.used_swap);
+
+        if let Some(total) = self.total_disk_io() {
+            if self.disk_io_history.len() == DISK_IO_HISTORY_LEN {
+                self.disk_io_history.pop_front();
+            }
+            self.disk_io_history.push_back(total);
+        }
+
+        let stealing = self
+            .snapshot
+            .cpus
+            .iter()
+            .any(|cpu| cpu.steal_pct.is_some_and(|steal| steal > 0.0));
+        self.steal_idle_samples = if stealing {
+            0
+        } else {
+            self.steal_idle_samples.saturating_add(1)
+        };
+
+        if self.cpu_history.len() == CPU_HISTORY_LEN {
+            self.cpu_history.pop_front();
+        }
+        self.cpu_history
+            .push_back(self.snapshot.mean_cpu_usage().round() as u64);
+
+        if let Some(swap_io) = self.snapshot.memory.swap_io {
+            if self.swap_io_history.len() == SWAP_HISTORY_LEN {
+                self.swap_io_history.pop_front();
+            }
+            self.swap_io_history.push_back(swap_io);
+            self.swap_io_seen |= swap_io.pages_in > 0.0 || swap_io.pages_out > 0.0;
+        }
+
+        if self.interface_state.selected().is_none() && !self.snapshot.networks.is_empty() {
+            self.interface_state.select(Some(0));
+        }
+        if self.partition_state.selected().is_none() && !self.snapshot.disks.is_empty() {
+            self.partition_state.select(Some(0));
+        }
+        if self.process_state.selected().is_none() && !self.snapshot.processes.is_empty() {
+            self.process_state.select(Some(0));
+        }
+    }
+
+    fn poll_benchmark(&mut self) {
+        let Some(benchmark) = &mut self.benchmark else {
+            return;
+        };
+
+        match benchmark.poll() {
+            Some(Ok(mb_per_sec)) => {
+                self.benchmark_results
+                    .insert(benchmark.device.clone(), mb_per_sec);
+                self.benchmark = None;
+            }
+            Some(Err(err)) => {
+                self.status = Some(format!("Benchmark of {} failed: {err}", benchmark.device));
+                self.benchmark = None;
+            }
+            None => {}
+        }
+    }
+
+    fn render_frame(&mut self, frame: &mut Frame) {
+        let area = frame.size();
+        if !self.layout.fits(area) {
+            let (min_width, min_height) = self.layout.min_size;
+            let warning = Paragraph::new(format!(
+                "Terminal too small (need ≥ {min_width}×{min_height}, have {}×{})",
+                area.width, area.height
+            ))
+            .centered()
+            .yellow();
+
+            frame.render_widget(warning, widgets::centered(area, area.width, 1));
+            return;
+        }
+
+        if !self.focused {
+            self.render_background(frame);
+        } else if self.fullscreen {
+            self.render_tab(frame, frame.size());
+        } else {
+            let outer_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![
+                    Constraint::Percentage(5),
+                    Constraint::Min(0),
+                    Constraint::Length(1),
+                ])
+                .split(frame.size());
+
+            self.timed("header", frame, |app, frame| {
+                app.render_header(frame, outer_layout[0])
+            });
+            self.render_tab(frame, outer_layout[1]);
+            self.timed("status bar", frame, |app, frame| {
+                app.render_status_bar(frame, outer_layout[2])
+            });
+        }
+
+        if self.env_viewer.is_some() {
+            self.timed("environment", frame, |app, frame| {
+                if let Some(viewer) = &mut app.env_viewer {
+                    frame.render_widget(viewer, frame.size())
+                }
+            });
+        }
+
+        if let Some(viewer) = &self.smaps_viewer {
+            frame.render_widget(viewer, frame.size());
+        }
+
+        if let Some(search) = &self.global_search {
+            frame.render_widget(search, frame.size());
+        }
+
+        if let Some(view) = &self.event_log_view {
+            view.render(&self.event_log, frame.size(), frame.buffer_mut());
+        }
+
+        if self.self_monitor {
+            frame.render_widget(&self.self_monitor_widget(), frame.size());
+        }
+
+        if let Some(input) = &self.threshold_input {
+            frame.render_widget(input, frame.size());
+        }
+    }
+
+    /// shown instead of the (stale) metrics while `--refresh-on-focus` has paused the worker
+    fn render_background(&self, frame: &mut Frame) {
+        let outer_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Percentage(5), Constraint::Min(0)])
+            .split(frame.size());
+
+        self.render_header(frame, outer_layout[0]);
+
+        let notice = Paragraph::new("[BACKGROUND - paused]").centered().dim();
+        frame.render_widget(
+            notice,
+            widgets::centered(outer_layout[1], outer_layout[1].width, 1),
+        );
+    }
+
+    /// our own usage as seen by the refresh worker, plus render statistics
+    fn self_monitor_widget(&self) -> SelfMonitor {
+        let pid = std::process::id();
+        let process = self
+            .snapshot
+            .processes
+            .iter()
+            .find(|process| process.pid == pid);
+
+        SelfMonitor {
+            cpu_usage: process.map(|process| process.cpu_usage),
+            memory: process.map(|process| process.memory),
+            frames: self.frames,
+            render_time: self.render_time,
+            runtime: self.started.elapsed(),
+            widget_timings: self
+                .render_timings
+                .iter()
+                .map(|(name, timings)| WidgetTiming {
+                    name,
+                    mean: timings.iter().sum::<Duration>() / timings.len().max(1) as u32,
+                    max: timings.iter().copied().max().unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+
+    fn render_tab(&mut self, frame: &mut Frame, area: Rect) {
+        match self.tab {
+            Tab::Cpu => self.render_widget_kind(frame, WidgetKind::Cpu, area),
+            Tab::Memory => self.render_widget_kind(frame, WidgetKind::Memory, area),
+            Tab::Network => self.render_widget_kind(frame, WidgetKind::Network, area),
+            Tab::Disk => self.render_widget_kind(frame, WidgetKind::Disk, area),
+            Tab::Processes => self.render_widget_kind(frame, WidgetKind::Processes, area),
+            Tab::Dashboard => self.render_dashboard(frame, area),
+            Tab::Plugin(index) => self.timed("plugin", frame, |app, frame| {
+                app.plugins.widgets()[index].render(area, frame.buffer_mut())
+            }),
+        }
+    }
+
+    fn render_widget_kind(&mut self, frame: &mut Frame, kind: WidgetKind, area: Rect) {
+        match kind {
+            WidgetKind::Cpu => self.timed("cpu", frame, |app, frame| app.render_cpus(frame, area)),
+            WidgetKind::Memory => {
+                self.timed("memory", frame, |app, frame| app.render_memory(frame, area))
+            }
+            WidgetKind::Network => self.timed("network", frame, |app, frame| {
+                app.render_network(frame, area)
+            }),
+            WidgetKind::Disk => {
+                self.timed("disks", frame, |app, frame| app.render_disks(frame, area))
+            }
+            WidgetKind::Processes => self.timed("processes", frame, |app, frame| {
+                app.render_processes(frame, area)
+            }),
+        }
+    }
+
+    /// the dashboard grid, or the one being edited with the focused widget outlined
+    fn render_dashboard(&mut self, frame: &mut Frame, area: Rect) {
+        let grid = match &self.layout_editor {
+            Some(editor) => editor.grid().clone(),
+            None => self.dashboard.clone(),
+        };
+        let areas = grid.areas(area);
+
+        for &(kind, widget_area) in &areas {
+            self.render_widget_kind(frame, kind, widget_area);
+        }
+
+        let Some(editor) = &self.layout_editor else {
+            return;
+        };
+        let focused = editor.focused().copied();
+        if let Some(&(kind, widget_area)) =
+            focused.and_then(|focused| areas.iter().find(|(kind, _)| *kind == focused.widget))
+        {
+            let outline = Block::new()
+                .title(format!(" {} (editing) ", kind.title()))
+                .borders(Borders::ALL)
+                .border_style(Style::new().yellow().bold());
+
+            frame.render_widget(outline, widget_area);
+        }
+    }
+
+    /// runs `render`, recording how long it took while the Ctrl+S overlay is open
+    fn timed(
+        &mut self,
+        name: &'static str,
+        frame: &mut Frame,
+        render: impl FnOnce(&mut Self, &mut Frame),
+    ) {
+        if !self.self_monitor {
+            render(self, frame);
+            return;
+        }
+
+        let start = Instant::now();
+        render(self, frame);
+        let elapsed = start.elapsed();
+
+        let timings = self.render_timings.entry(name).or_default();
+        if timings.len() == RENDER_TIMING_SAMPLES {
+            timings.pop_front();
+        }
+        timings.push_back(elapsed);
+    }
+
+    fn render_header(&self, frame: &mut Frame, area: Rect) {
+        let contention_badge = self
+            .snapshot
+            .cpu_contention
+            .filter(|contention| *contention > CONTENTION_WARNING_PCT)
+            .map(|contention| format!(" contention {contention:.0}% "));
+
+        let header_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Min(0),
+                Constraint::Length(
+                    contention_badge
+                        .as_ref()
+                        .map_or(0, |badge| badge.len() as u16),
+                ),
+                Constraint::Length(self.name.len() as u16 + 2),
+                Constraint::Length(8),
+            ])
+            .split(area);
+
+        let tabs = self.tabs();
+        let selected = tabs.iter().position(|tab| *tab == self.tab).unwrap_or(0);
+
+        let tabs = Tabs::new(tabs.into_iter().map(|tab| self.tab_label(tab)))
+            .select(selected)
+            .highlight_style(Style::new().bold().reversed());
+
+        frame.render_widget(tabs, header_layout[0]);
+        if let Some(badge) = contention_badge {
+            frame.render_widget(Paragraph::new(badge).black().on_yellow(), header_layout[1]);
+        }
+        frame.render_widget(
+            Paragraph::new(self.name.as_str()).alignment(Alignment::Center),
+            header_layout[2],
+        );
+        frame.render_widget(&self.clock, header_layout[3]);
+    }
+
+    fn render_cpus(&self, frame: &mut Frame, area: Rect) {
+        #[cfg(feature = "perf_events")]
+        let area = {
+            let cpu_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![Constraint::Min(0), Constraint::Length(9)])
+                .split(area);
+
+            self.render_syscall_rate(frame, cpu_layout[1]);
+            cpu_layout[0]
+        };
+
+        #[cfg(feature = "rapl")]
+        let area = match rapl_line(&self.snapshot.power) {
+            Some(line) => {
+                let cpu_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(vec![Constraint::Min(0), Constraint::Length(1)])
+                    .split(area);
+
+                frame.render_widget(Paragraph::new(line), cpu_layout[1]);
+                cpu_layout[0]
+            }
+            None => area,
+        };
+
+        let area = {
+            let cpu_layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![Constraint::Min(0), Constraint::Length(1)])
+                .split(area);
+
+            let [one, five, fifteen] = self.snapshot.load_average;
+            let mut text = if self.capabilities.load_average {
+                format!("Load average: {one:.2} {five:.2} {fifteen:.2}")
+            } else {
+                "Load average: not available on this system".to_string()
+            };
+            if let Some(contention) = self.snapshot.cpu_contention {
+                text +=
+                    &format!(" | Contention: {contention:.1}% (iowait + steal + irq + softirq)");
+            }
+
+            let line = Paragraph::new(text);
+            let contended = self
+                .snapshot
+                .cpu_contention
+                .is_some_and(|contention| contention > CONTENTION_WARNING_PCT);
+            frame.render_widget(
+                if contended { line.yellow() } else { line.dim() },
+                cpu_layout[1],
+            );
+            cpu_layout[0]
+        };
+
+        let cpus = &self.snapshot.cpus;
+        if cpus.is_empty() {
+            return;
+        }
+
+        if let Some(core) = self.cpu_zoom {
+            if let Some(cpu) = cpus.get(core) {
+                self.render_cpu_zoom(frame, core, cpu, area);
+                return;
+            }
+        }
+
+        let area = match self.layout.mode(frame.size()) {
+            LayoutMode::Compact if self.cpu_temperatures => {
+                self.render_temperature_heatmap(frame, area);
+                return;
+            }
+            LayoutMode::Compact => {
+                self.render_cpu_summary(frame, area);
+                return;
+            }
+            LayoutMode::Medium => area,
+            LayoutMode::Full => {
+                let cpu_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(vec![Constraint::Min(0), Constraint::Length(8)])
+                    .split(area);
+
+                self.render_cpu_history(frame, cpu_layout[1]);
+                cpu_layout[0]
+            }
+        };
+
+        let area = match &self.cpu_topology {
+            // a topology that does not match the cores sysinfo reports would mix up siblings
+            Some(topology) if topology.logical_cpus() == cpus.len() => {
+                let cpu_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(vec![Constraint::Length(3), Constraint::Min(0)])
+                    .split(area);
+
+                self.render_physical_cpu(frame, topology, cpu_layout[0]);
+                cpu_layout[1]
+            }
+            _ => area,
+        };
+
+        if self.cpu_temperatures {
+            self.render_temperature_heatmap(frame, area);
+            return;
+        }
+
+        let columns = cpu_columns_for_width(area.width, cpus.len(), self.cpu_config);
+        let rows = cpus.len().div_ceil(columns);
+
+        let row_areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+            .split(area);
+
+        for (row, chunk) in cpus.chunks(columns).enumerate() {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+                .split(row_areas[row]);
+
+            for (column, cpu) in chunk.iter().enumerate() {
+                self.render_cpu(frame, row * columns + column, cpu, cells[column]);
+            }
+        }
+    }
+
+    /// per-core temperatures on the same grid as the usage bars, colored by how hot they are
+    fn render_temperature_heatmap(&self, frame: &mut Frame, area: Rect) {
+        if !self.capabilities.temperature {
+            render_unavailable(frame, area, "Core temperatures");
+            return;
+        }
+
+        let temperatures = &self.snapshot.core_temperatures;
+        if temperatures.is_empty() {
+            let block = Block::new()
+                .title("Core temperatures")
+                .borders(Borders::ALL);
+            frame.render_widget(
+                Paragraph::new("Temperature data not available for heatmap")
+                    .dim()
+                    .block(block),
+                area,
+            );
+            return;
+        }
+
+        let columns = cpu_columns_for_width(area.width, temperatures.len(), self.cpu_config);
+        let rows = temperatures.len().div_ceil(columns);
+
+        let row_areas = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, rows as u32); rows])
+            .split(area);
+
+        for (row, chunk) in temperatures.chunks(columns).enumerate() {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+                .split(row_areas[row]);
+
+            for (column, celsius) in chunk.iter().enumerate() {
+                let block = Block::new()
+                    .title(format!("Core {}", row * columns + column))
+                    .borders(Borders::ALL);
+                let cell = match celsius {
+                    Some(celsius) => Paragraph::new(format!("{celsius:.1} °C")).style(
+                        Style::new()
+                            .bg(cpu_temperature::temperature_color(*celsius))
+                            .black()
+                            .bold(),
+                    ),
+                    None => Paragraph::new("n/a").dim(),
+                };
+                frame.render_widget(cell.block(block), cells[column]);
+            }
+        }
+    }
+
+    /// a single core across the whole area, with its cache sizes below it
+    fn render_cpu_zoom(&self, frame: &mut Frame, index: usize, cpu: &CpuSnapshot, area: Rect) {
+        let cpu_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Min(0), Constraint::Length(1)])
+            .split(area);
+
+        self.render_cpu(frame, index, cpu, cpu_layout[0]);
+
+        let caches = match self.cpu_caches.get(index) {
+            Some(summary) if !summary.is_empty() => summary.as_str(),
+            _ => "cache info not available",
+        };
+        frame.render_widget(
+            Paragraph::new(format!(
+                "{caches} | core {}/{} (←/→ other cores, Enter all cores)",
+                index + 1,
+                self.snapshot.cpus.len()
+            ))
+            .dim(),
+            cpu_layout[1],
+        );
+    }
+
+    /// one gauge with the mean usage of all cores
+    fn render_cpu_summary(&self, frame: &mut Frame, area: Rect) {
+        let usage = self.snapshot.mean_cpu_usage();
+
+        let mut block = Block::new().borders(Borders::ALL);
+        if usage > self.thresholds.cpu {
+            block = block.border_style(Style::new().red());
+        }
+
+        let gauge = Gauge::default()
+            .block(block)
+            .gauge_style(Style::new().green())
+            .label(format!(
+                "CPU ({} cores): {usage:.0}%{}",
+                self.snapshot.cpus.len(),
+                self.trend(|snapshot| Some(f64::from(snapshot.mean_cpu_usage())))
+                    .content
+            ))
+            .ratio((f64::from(usage) / 100.0).clamp(0.0, 1.0));
+
+        frame.render_widget(
+            gauge,
+            Rect {
+                height: area.height.min(3),
+                ..area
+            },
+        );
+    }
+
+    /// mean usage of the physical cores, see [`cpu_topology::physical_cpu_usage`]
+    fn render_physical_cpu(&self, frame: &mut Frame, topology: &CpuTopology, area: Rect) {
+        let usage = cpu_topology::physical_cpu_usage(&self.snapshot.cpus, topology);
+
+        let gauge = Gauge::default()
+            .block(Block::new().title("Physical CPU").borders(Borders::ALL))
+            .gauge_style(Style::new().cyan())
+            .label(format!(
+                "HT-adjusted: {usage:.0}% ({} cores, {} threads)",
+                topology.physical_cores(),
+                topology.logical_cpus()
+            ))
+            .ratio((f64::from(usage) / 100.0).clamp(0.0, 1.0));
+
+        frame.render_widget(gauge, area);
+    }
+
+    fn render_cpu_history(&self, frame: &mut Frame, area: Rect) {
+        let history: Vec<u64> = self.cpu_history.iter().copied().collect();
+
+        let usage = self.snapshot.mean_cpu_usage();
+        let contention = match self.snapshot.cpu_contention {
+            Some(contention) => format!(", {contention:.0}% lost to contention)"),
+            None => ")".to_string(),
+        };
+        let title = Line::from(vec![
+            Span::from(format!("CPU history (now {usage:.0}%")),
+            self.trend(|snapshot| Some(f64::from(snapshot.mean_cpu_usage()))),
+            Span::from(contention),
+        ]);
+
+        let sparkline = GradientSparkline::new(&history, &self.gradient)
+            .block(Block::new().title(title).borders(Borders::ALL))
+            .max(100);
+
+        frame.render_widget(sparkline, area);
+    }
+
+    fn render_cpu(&self, frame: &mut Frame, index: usize, cpu: &CpuSnapshot, area: Rect) {
+        let mut cpu_block = Block::new().title(cpu.name.as_str()).borders(Borders::ALL);
+
+        if cpu.usage > self.thresholds.cpu {
+            cpu_block = cpu_block.border_style(Style::new().red());
+        }
+
+        let mut inner = cpu_block.inner(area);
+        frame.render_widget(cpu_block, area);
+
+        // a red band on the first row, hidden on hosts that never see any steal
+        if let Some(steal) = cpu.steal_pct {
+            if self.steal_idle_samples < STEAL_IDLE_SAMPLES && inner.height > 1 {
+                let band = Rect { height: 1, ..inner };
+                let filled = (f32::from(band.width) * steal / 100.0).ceil() as u16;
+
+                frame.render_widget(Paragraph::new(format!("st {steal:.1}%")).red(), band);
+                frame.buffer_mut().set_style(
+                    Rect {
+                        width: filled.min(band.width),
+                        ..band
+                    },
+                    Style::new().on_red().white(),
+                );
+
+                inner.y += 1;
+                inner.height -= 1;
+            }
+        }
+
+        let active = cpu.usage.clamp(0.0, 100.0);
+        let iowait = cpu.iowait_pct.unwrap_or(0.0).clamp(0.0, 100.0 - active);
+
+        let label = if iowait > 0.0 {
+            format!("{active:.1}% (io {iowait:.1}%)")
+        } else {
+            format!("{active:.1}%")
+        };
+        let style = self.diff_style(|snapshot| Some(snapshot.cpus.get(index)?.usage), false);
+        frame.render_widget(Paragraph::new(label).style(style), inner);
+
+        // usage fills from the bottom, iowait is stacked on top of it
+        let bar = Rect {
+            y: inner.y + 1,
+            height: inner.height.saturating_sub(1),
+            ..inner
+        };
+        let rows =
+            |pct: f32| ((f32::from(bar.height) * pct / 100.0).round() as u16).min(bar.height);
+        let active_rows = rows(active);
+        let stacked_rows = rows(active + iowait).max(active_rows);
+
+        let buf = frame.buffer_mut();
+        buf.set_style(
+            Rect {
+                y: bar.bottom() - active_rows,
+                height: active_rows,
+                ..bar
+            },
+            Style::new().on_green(),
+        );
+        buf.set_style(
+            Rect {
+                y: bar.bottom() - stacked_rows,
+                height: stacked_rows - active_rows,
+                ..bar
+            },
+            Style::new().on_yellow(),
+        );
+    }
+
+    #[cfg(feature = "perf_events")]
+    fn render_syscall_rate(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::new().title("Context switches").borders(Borders::ALL);
+
+        let Some(context_switches) = &self.snapshot.context_switches else {
+            frame.render_widget(Paragraph::new("not available").block(block), area);
+            return;
+        };
+
+        let total = match context_switches.per_sec {
+            Some(per_sec) => format!("{per_sec:.0}/s"),
+            None => "sampling...".to_string(),
+        };
+
+        let rows = context_switches.top.iter().map(|process| {
+            Row::new(vec![
+                process.pid.to_string(),
+                process.name.clone(),
+                format!("{:.0}/s", process.voluntary),
+                format!("{:.0}/s", process.involuntary),
+            ])
+        });
+
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Min(16),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(Row::new(vec!["PID", "Name", "Voluntary", "Involuntary"]).bold())
+            .block(block.title(format!("System: {total}")));
+
+        frame.render_widget(table, area)
+    }
+
+    fn render_memory(&self, frame: &mut Frame, area: Rect) {
+        let memory = &self.snapshot.memory;
+
+        let has_swap = memory.total_swap > 0;
+
+        let memory_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(4),
+                Constraint::Length(if has_swap { 3 } else { 1 }),
+                Constraint::Length(match (has_swap, self.swap_io_seen) {
+                    (false, _) => 0,
+                    (true, false) => 1,
+                    (true, true) => 4,
+                }),
+                Constraint::Length(if has_swap { 6 } else { 0 }),
+                Constraint::Min(0),
+            ])
+            .split(area);
+
+        let used_pct = memory_used_pct(memory);
+        let style = self.diff_style(
+            |snapshot| Some(memory_used_pct(&snapshot.memory) as f32),
+            false,
+        );
+
+        let mut memory_block = Block::new()
+            .title(
+                Line::from(vec![
+                    Span::from(format!("Memory {}", format_bytes(memory.used))),
+                    self.trend(|snapshot| Some(snapshot.memory.used as f64)),
+                    Span::from(format!(" / {}", format_bytes(memory.total))),
+                ])
+                .style(style),
+            )
+            .borders(Borders::ALL);
+
+        if used_pct > self.thresholds.memory {
+            memory_block = memory_block.border_style(Style::new().red());
+        }
+
+        let bar_area = memory_block.inner(memory_layout[0]);
+        frame.render_widget(memory_block, memory_layout[0]);
+        frame.render_widget(&MemoryBar::new(memory), bar_area);
+
+        if !has_swap {
+            frame.render_widget(
+                Paragraph::new(" No swap configured").dim(),
+                memory_layout[1],
+            );
+            return;
+        }
+
+        let swap_ratio = memory.used_swap as f64 / memory.total_swap as f64;
+
+        let swap = Gauge::default()
+            .block(Block::new().title("Swap").borders(Borders::ALL))
+            .gauge_style(Style::new().magenta())
+            .label(format!(
+                "{} / {}",
+                format_bytes(memory.used_swap),
+                format_bytes(memory.total_swap)
+            ))
+            .ratio(swap_ratio.clamp(0.0, 1.0));
+
+        frame.render_widget(swap, memory_layout[1]);
+        self.render_swap_io(frame, memory_layout[2]);
+
+        let history: Vec<u64> = self.swap_history.iter().copied().collect();
+        let peak = history.iter().copied().max().unwrap_or_default();
+
+        let sparkline = GradientSparkline::new(&history, &self.gradient)
+            .block(
+                Block::new()
+                    .title(format!(
+                        "Swap history (max {}, now {})",
+                        format_bytes(peak),
+                        format_bytes(memory.used_swap)
+                    ))
+                    .borders(Borders::ALL),
+            )
+            .max(memory.total_swap);
+
+        frame.render_widget(sparkline, memory_layout[3]);
+    }
+
+    /// swap-in / swap-out gauges scaled to the peak rate of the last minute
+    fn render_swap_io(&self, frame: &mut Frame, area: Rect) {
+        if !self.swap_io_seen {
+            frame.render_widget(Paragraph::new(" Swap I/O: idle").dim(), area);
+            return;
+        }
+
+        let peak = self
+            .swap_io_history
+            .iter()
+            .map(|swap_io| swap_io.pages_in.max(swap_io.pages_out))
+            .fold(0.0, f64::max);
+        let current = self.swap_io_history.back().copied().unwrap_or_default();
+
+        let block = Block::new().title("Swap I/O").borders(Borders::ALL);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(1), Constraint::Length(1)])
+            .split(block.inner(area));
+        frame.render_widget(block, area);
+
+        for (row, label, pages) in [
+            (rows[0], "in", current.pages_in),
+            (rows[1], "out", current.pages_out),
+        ] {
+            let ratio = if peak > 0.0 { pages / peak } else { 0.0 };
+            let gauge = Gauge::default()
+                .gauge_style(Style::new().magenta())
+                .label(format!(
+                    "{label} {pages:.0} pages/s ({:.1} MiB/s)",
+                    pages * SWAP_PAGE_SIZE / (1024.0 * 1024.0)
+                ))
+                .ratio(ratio.clamp(0.0, 1.0));
+
+            frame.render_widget(gauge, row);
+        }
+    }
+
+    fn render_network(&mut self, frame: &mut Frame, area: Rect) {
+        let network_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Min(3),
+                Constraint::Length(5),
+                Constraint::Length(if self.snapshot.ping.is_some() { 3 } else { 0 }),
+                Constraint::Length(match self.snapshot.dns.len() {
+                    0 => 0,
+                    hosts => hosts as u16 + 3,
+                }),
+            ])
+            .split(area);
+
+        let threshold_bps = self.thresholds.network * 1024.0 * 1024.0;
+
+        let rows = self
+            .snapshot
+            .networks
+            .iter()
+            .filter(|interface| matches_filter(&self.filter, &interface.name))
+            .map(|interface| {
+                let rate = |rate: fn(&net::InterfaceStats) -> f64| {
+                    let style = self.diff_style(
+                        |snapshot| {
+                            let stats = snapshot
+                                .networks
+                                .iter()
+                                .find(|stats| stats.name == interface.name)?;
+                            Some(rate(stats) as f32)
+                        },
+                        true,
+                    );
+
+                    let trend = self.trend(|snapshot| {
+                        let stats = snapshot
+                            .networks
+                            .iter()
+                            .find(|stats| stats.name == interface.name)?;
+                        // compare whole bytes as displayed, so idle links do not flicker
+                        Some(rate(stats).floor())
+                    });
+
+                    Line::from(vec![
+                        Span::styled(format_bytes_per_sec(rate(interface)), style),
+                        trend,
+                    ])
+                };
+
+                let address = self
+                    .interface_addresses
+                    .get(&interface.name)
+                    .and_then(|addresses| net::addresses::primary_address(addresses));
+
+                let mtu = match self.interface_mtus.get(&interface.name) {
+                    Some(mtu) => mtu.to_string(),
+                    None => "-".to_string(),
+                };
+                let mtu = if self
+                    .mtu_warnings
+                    .iter()
+                    .any(|warning| warning.interface == interface.name)
+                {
+                    Line::from(vec![Span::from(mtu), Span::from(" ⚠").yellow()])
+                } else {
+                    Line::from(mtu)
+                };
+
+                let row = Row::new(vec![
+                    Line::from(interface.name.as_str()),
+                    match address {
+                        Some(address) => Line::from(address.to_string()),
+                        None => Line::from("no IPv4").dim(),
+                    },
+                    mtu,
+                    rate(|stats| stats.rx_bps),
+                    rate(|stats| stats.tx_bps),
+                    Line::from(format_bytes(interface.total_rx)),
+                    Line::from(format_bytes(interface.total_tx)),
+                ]);
+
+                if interface.rx_bps + interface.tx_bps > threshold_bps {
+                    row.red()
+                } else {
+                    row
+                }
+            });
+
+        let widths = [
+            Constraint::Percentage(16),
+            Constraint::Percentage(20),
+            Constraint::Length(7),
+            Constraint::Percentage(14),
+            Constraint::Percentage(14),
+            Constraint::Percentage(14),
+            Constraint::Percentage(14),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(vec![
+                    "Interface",
+                    "Address",
+                    "MTU",
+                    "RX",
+                    "TX",
+                    "Total RX",
+                    "Total TX",
+                ])
+                .bold(),
+            )
+            .block(
+                Block::new()
+                    .title(self.table_title("Network"))
+                    .borders(Borders::ALL),
+            )
+            .highlight_style(Style::new().reversed());
+
+        if self.capabilities.networks {
+            frame.render_stateful_widget(table, network_layout[0], &mut self.interface_state);
+        } else {
+            render_unavailable(frame, network_layout[0], "Network");
+        }
+
+        self.render_interface_details(frame, network_layout[1]);
+        self.render_connectivity(frame, network_layout[2]);
+        self.render_dns(frame, network_layout[3]);
+    }
+
+    fn render_dns(&self, frame: &mut Frame, area: Rect) {
+        if self.snapshot.dns.is_empty() {
+            return;
+        }
+
+        let rows = self.snapshot.dns.iter().map(|result| {
+            let time = result
+                .resolve_ms
+                .map_or("-".to_string(), |ms| format!("{ms:.1} ms"));
+            let row = Row::new(vec![
+                result.host.clone(),
+                time,
+                result.status.label().to_string(),
+            ]);
+
+            match (result.status, result.resolve_ms) {
+                (dns::DnsStatus::Pending, _) => row,
+                (dns::DnsStatus::Ok, Some(ms)) if ms < 10.0 => row.green(),
+                (dns::DnsStatus::Ok, Some(ms)) if ms <= 100.0 => row.yellow(),
+                _ => row.red(),
+            }
+        });
+
+        let widths = [
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(Row::new(vec!["Host", "Resolve time", "Status"]).bold())
+            .block(Block::new().title("DNS").borders(Borders::ALL));
+
+        frame.render_widget(table, area);
+    }
+
+    /// `--ping` results; the border flashes red while loss is above the threshold
+    fn render_connectivity(&self, frame: &mut Frame, area: Rect) {
+        let Some(ping) = &self.snapshot.ping else {
+            return;
+        };
+
+        let mut block = Block::new()
+            .title(format!("Connectivity: {}", ping.host))
+            .borders(Borders::ALL);
+
+        let flash_on = (self.started.elapsed().as_millis() / 500).is_multiple_of(2);
+        if ping.loss_pct > self.thresholds.packet_loss && flash_on {
+            block = block.border_style(Style::new().red());
+        }
+
+        let text = match &ping.error {
+            Some(error) => error.clone(),
+            None => {
+                let rtt = ping
+                    .rtt_ms
+                    .map_or("-".to_string(), |rtt| format!("{rtt:.1} ms"));
+                let method = match ping.method {
+                    ping::ProbeMethod::Icmp => "ICMP",
+                    ping::ProbeMethod::Tcp => "TCP :443",
+                };
+
+                format!("RTT: {rtt} | Loss: {:.1}% | via {method}", ping.loss_pct)
+            }
+        };
+
+        frame.render_widget(Paragraph::new(text).block(block), area);
+    }
+
+    fn render_interface_details(&self, frame: &mut Frame, area: Rect) {
+        let Some(interface) = self.selected_interface() else {
+            return;
+        };
+
+        let lines = match &self.interface_details {
+            Some((name, Ok(details))) if *name == interface.name => interface_detail_lines(details),
+            Some((name, Err(err))) if *name == interface.name => {
+                vec![Line::from(format!("Could not read details: {err}"))]
+            }
+            _ => vec![],
+        };
+
+        let mut block = Block::new()
+            .title(interface.name.as_str())
+            .borders(Borders::ALL);
+        if let Some(warning) = self
+            .mtu_warnings
+            .iter()
+            .find(|warning| warning.interface == interface.name)
+        {
+            block = block.title_bottom(Line::from(format!("⚠ {}", warning.message)).yellow());
+        }
+
+        frame.render_widget(Paragraph::new(lines).block(block), area)
+    }
+
+    /// combined throughput of the partitions matching the search, each device counted once
+    fn total_disk_io(&self) -> Option<DiskIo> {
+        let mut devices = HashSet::new();
+
+        self.snapshot
+            .disks
+            .iter()
+            .filter(|partition| partition_matches(&self.filter, partition))
+            .filter(|partition| devices.insert(partition.name.as_str()))
+            .filter_map(|partition| partition.io)
+            .reduce(|total, io| DiskIo {
+                read_bps: total.read_bps + io.read_bps,
+                write_bps: total.write_bps + io.write_bps,
+            })
+    }
+
+    fn render_disks(&mut self, frame: &mut Frame, area: Rect) {
+        if !self.capabilities.disks {
+            render_unavailable(frame, area, "Disks");
+            return;
+        }
+
+        let disk_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(TotalDiskIoWidget::HEIGHT),
+                Constraint::Min(0),
+            ])
+            .split(area);
+
+        let peak = self
+            .disk_io_history
+            .iter()
+            .map(|io| io.read_bps.max(io.write_bps))
+            .fold(0.0, f64::max);
+        let total_io = TotalDiskIoWidget {
+            total: self.total_disk_io(),
+            peak,
+        };
+        frame.render_widget(&total_io, disk_layout[0]);
+        let area = disk_layout[1];
+
+        let selected = self.partition_state.selected().and_then(|index| {
+            self.snapshot
+                .disks
+                .iter()
+                .filter(|partition| partition_matches(&self.filter, partition))
+                .nth(index)
+        });
+        let area = match selected {
+            Some(partition) => {
+                let disk_layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(vec![Constraint::Min(0), Constraint::Length(3)])
+                    .split(area);
+
+                render_mount_options(frame, partition, disk_layout[1]);
+                disk_layout[0]
+            }
+            None => area,
+        };
+
+        let rows = self
+            .snapshot
+            .disks
+            .iter()
+            .filter(|partition| partition_matches(&self.filter, partition))
+            .map(|partition| {
+                let device = match self.benchmark_results.get(&partition.name) {
+                    Some(mb_per_sec) => format!("{} [{mb_per_sec:.0} MB/s]", partition.name),
+                    None => partition.name.clone(),
+                };
+
+                let rate = |io: &DiskIo, rate: fn(&DiskIo) -> f64| {
+                    let style = self.diff_style(
+                        |snapshot| {
+                            let stats = snapshot
+                                .disks
+                                .iter()
+                                .find(|stats| stats.mount_point == partition.mount_point)?;
+                            Some(rate(stats.io.as_ref()?) as f32)
+                        },
+                        true,
+                    );
+
+                    Span::styled(format!("{}/s", format_bytes_short(rate(io) as u64)), style)
+                };
+                let throughput = match &partition.io {
+                    Some(io) => Line::from(vec![
+                        rate(io, |io| io.read_bps),
+                        Span::from(" "),
+                        rate(io, |io| io.write_bps),
+                    ]),
+                    None => Line::from("N/A").dim(),
+                };
+
+                let mut device = vec![Span::from(device)];
+                if let Some(endurance) = partition.endurance {
+                    let remaining = endurance.remaining_pct;
+                    let badge = Span::from(format!(" Endurance: {remaining:.0}%"));
+                    device.push(match remaining {
+                        remaining if remaining > 50.0 => badge.green(),
+                        remaining if remaining >= 10.0 => badge.yellow(),
+                        _ => badge.red(),
+                    });
+                }
+
+                let row = Row::new(vec![
+                    Line::from(device),
+                    Line::from(partition.mount_point.display().to_string()),
+                    Line::from(partition.file_system.as_str()),
+                    Line::from(vec![
+                        Span::from(format_bytes(partition.used())),
+                        self.trend(|snapshot| {
+                            let stats = snapshot
+                                .disks
+                                .iter()
+                                .find(|stats| stats.mount_point == partition.mount_point)?;
+                            Some(stats.used() as f64)
+                        }),
+                    ]),
+                    Line::from(format_bytes(partition.total)),
+                    Line::from(format!("{:.1}%", partition.used_pct())),
+                    throughput,
+                    Line::from(inode_usage(partition)),
+                ]);
+
+                let inodes_pct = partition.inodes_used_pct().unwrap_or(0.0);
+                if partition.used_pct() > self.thresholds.disk || inodes_pct > self.thresholds.disk
+                {
+                    row.red()
+                } else {
+                    row
+                }
+            });
+
+        let widths = [
+            Constraint::Percentage(20),
+            Constraint::Percentage(16),
+            Constraint::Percentage(8),
+            Constraint::Length(11), // fits "1023.9 GiB" plus the trend arrow
+            Constraint::Percentage(10),
+            Constraint::Percentage(8),
+            Constraint::Length(15), // fits "1023B/s 1023B/s"
+            Constraint::Min(29),    // fits "Inodes: 11.9% (15.1K/128.0K)"
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(vec![
+                    "Device",
+                    "Mount",
+                    "FS",
+                    "Used",
+                    "Total",
+                    "Use%",
+                    "Read / Write",
+                    "Inodes",
+                ])
+                .bold(),
+            )
+            .block(
+                Block::new()
+                    .title(self.table_title("Disks"))
+                    .borders(Borders::ALL),
+            )
+            .highlight_style(Style::new().reversed());
+
+        frame.render_stateful_widget(table, area, &mut self.partition_state);
+    }
+
+    /// visible processes matching the search, the user filter and the age zoom,
+    /// as listed in the table
+    fn listed_processes(&self) -> impl Iterator<Item = &ProcessStats> {
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+
+        self.visible_processes().filter(move |process| {
+            let in_zoom = self.age_zoom.is_none_or(|bucket| {
+                AgeBucket::from_age(now.saturating_sub(process.start_time)) == bucket
+            });
+            let owned =
+                !self.user_only || (process.uid.is_some() && process.uid == self.current_uid);
+
+            in_zoom && owned && matches_filter(&self.filter, &process.name)
+        })
+    }
+
+    /// processes not hidden by `hide_names` / `hide_kernel_threads`
+    fn visible_processes(&self) -> impl Iterator<Item = &ProcessStats> {
+        let config = &self.process_config;
+
+        self.snapshot.processes.iter().filter(|process| {
+            let hidden_kernel_thread = config.hide_kernel_threads && process.kernel_thread;
+            let hidden_name = config
+                .hide_names
+                .iter()
+                .any(|prefix| process.name.starts_with(prefix.as_str()));
+
+            !hidden_kernel_thread && !hidden_name
+        })
+    }
+
+    fn render_processes(&mut self, frame: &mut Frame, area: Rect) {
+        let columns = &self.process_config.columns;
+
+        // older than 1.5 refresh intervals, e.g. because the process walk is lagging
+        let age_ms = chrono::Utc::now().timestamp_millis() - self.snapshot.processes_updated_ms;
+        let stale = age_ms as f64 > self.process_config.refresh_interval_secs * 1500.0;
+        let process_title = self.process_table_title(stale);
+        let hidden = self.snapshot.processes.len() - self.visible_processes().count();
+
+        // six bars plus borders, left out where the table would get too short
+        let histogram_height = if area.height >= 24 { 8 } else { 0 };
+        let process_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![
+                Constraint::Length(histogram_height),
+                Constraint::Min(0),
+                Constraint::Length(if hidden > 0 { 1 } else { 0 }),
+            ])
+            .split(area);
+
+        if histogram_height > 0 {
+            let now = chrono::Utc::now().timestamp().max(0) as u64;
+            let mut histogram = ProcessAgeWidget::new(
+                self.visible_processes()
+                    .map(|process| now.saturating_sub(process.start_time)),
+            );
+            histogram.selected = self.age_bucket_selected;
+            histogram.zoomed = self.age_zoom;
+
+            frame.render_widget(&histogram, process_layout[0]);
+        }
+
+        let rows = self.listed_processes().map(|process| {
+            let row = Row::new(columns.iter().map(|column| match column {
+                ProcessColumn::Pid => process.pid.to_string(),
+                ProcessColumn::Name => match &process.container {
+                    Some(container) => format!("{} [{container}]", process.name),
+                    None => process.name.clone(),
+                },
+                ProcessColumn::Cpu => format!("{:.1}%", process.cpu_usage),
+                ProcessColumn::Memory => format_bytes(process.memory),
+                ProcessColumn::Vcs => format!("{:.0}", process.voluntary_switches),
+                ProcessColumn::Nvcs => format!("{:.0}", process.involuntary_switches),
+                ProcessColumn::Affinity => process.cpu_affinity.clone(),
+            }));
+
+            if process.cpu_usage > self.thresholds.cpu {
+                row.red()
+            } else {
+                row
+            }
+        });
+
+        let widths = columns.iter().map(|column| match column {
+            ProcessColumn::Name => Constraint::Min(16),
+            _ => Constraint::Length(10),
+        });
+
+        let header = columns.iter().map(|column| match column {
+            ProcessColumn::Pid => "PID",
+            ProcessColumn::Name => "Name",
+            ProcessColumn::Cpu => "CPU%",
+            ProcessColumn::Memory => "Memory",
+            ProcessColumn::Vcs => "vCS/s",
+            ProcessColumn::Nvcs => "nvCS/s",
+            ProcessColumn::Affinity => "Affinity",
+        });
+
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).bold())
+            .block(Block::new().title(process_title).borders(Borders::ALL))
+            .highlight_style(Style::new().reversed());
+
+        frame.render_stateful_widget(table, process_layout[1], &mut self.process_state);
+
+        if hidden > 0 {
+            let footnote = Paragraph::new(format!("({hidden} processes hidden)")).dim();
+            frame.render_widget(footnote, process_layout[2]);
+        }
+    }
+
+    /// like [`Self::table_title`], combined with the user filter and the age zoom,
+    /// e.g. `Processes [user + search: foo] [age: 1–24 h]`
+    ///
+    /// The zoom is named here too since short terminals leave out the histogram.
+    fn process_table_title(&self, stale: bool) -> String {
+        let title = if stale {
+            "Processes (stale)"
+        } else {
+            "Processes"
+        };
+        let searching = !self.filter.is_empty() || self.editing_filter;
+
+        let title = match (self.user_only, searching) {
+            (true, true) => format!("{title} [user + search: {}]", self.filter),
+            (true, false) => format!("{title} [user filter]"),
+            (false, _) => self.table_title(title),
+        };
+        match self.age_zoom {
+            Some(bucket) => format!("{title} [age: {}]", bucket.label()),
+            None => title,
+        }
+    }
+
+    /// appends the active search to a table title, e.g. `Disks [search: nvme]`
+    fn table_title(&self, title: &str) -> String {
+        if self.filter.is_empty() && !self.editing_filter {
+            title.to_string()
+        } else {
+            format!("{title} [search: {}]", self.filter)
+        }
+    }
+
+    fn render_status_bar(&self, frame: &mut Frame, area: Rect) {
+        if let Some(benchmark) = &self.benchmark {
+            let gauge = Gauge::default()
+                .gauge_style(Style::new().cyan())
+                .label(format!("Benchmarking {}…", benchmark.device))
+                .ratio(benchmark.progress.clamp(0.0, 1.0));
+
+            frame.render_widget(gauge, area);
+            return;
+        }
+
+        let text = if self.layout_editor.is_some() {
+            Line::from(
+                "layout: Tab next widget | arrows move | w widen | s shrink | Enter save | Esc cancel",
+            )
+            .yellow()
+        } else if self.editing_filter {
+            Line::from(format!("/{}_", self.filter))
+        } else if self.confirm_config_overwrite {
+            let path = config::default_path().unwrap_or_default();
+            Line::from(format!("{} exists, overwrite it? y/n", path.display())).yellow()
+        } else if let Some(banner) = &self.oom_banner {
+            Line::from(banner.as_str()).white().on_red().bold()
+        } else if let Some(status) = &self.status {
+            Line::from(status.as_str()).yellow()
+        } else if self.paused {
+            Line::from("[paused] p resume").yellow()
+        } else if let Tab::Plugin(index) = self.tab {
+            Line::from(format!(
+                "q quit | Tab switch tab | {}",
+                self.plugins.widgets()[index].key_hint()
+            ))
+            .dim()
+        } else {
+            let tab_keys = match self.tab {
+                Tab::Disk => " | a set threshold | B benchmark disk",
+                Tab::Processes => {
+                    " | v environment | m memory map | o open in top | K kernel threads | u own processes | ←/→ Enter zoom to age"
+                }
+                Tab::Dashboard => " | E edit layout (event log is L)",
+                Tab::Cpu => " | a set threshold | Enter zoom to core | T temperatures",
+                _ => " | a set threshold",
+            };
+
+            Line::from(format!(
+                "q quit | Tab switch tab | / search | f fullscreen | p pause | ^F find | L event log | n title{tab_keys}"
+            ))
+            .dim()
+        };
+
+        frame.render_widget(Paragraph::new(text), area)
+    }
+
+    /// switches between the metrics window title and the original one
+    fn toggle_title(&mut self) {
+        self.title_active = !self.title_active;
+
+        let result = if self.title_active {
+            tui::push_title().and_then(|()| self.update_title())
+        } else {
+            tui::pop_title()
+        };
+        if let Err(err) = result {
+            self.status = Some(format!("setting the window title failed: {err}"));
+        }
+    }
+
+    fn update_title(&self) -> io::Result<()> {
+        tui::set_title(&once::render(&self.title_format, &self.snapshot))
+    }
+
+    /// shows `message` in the status bar until it is replaced or `duration` has passed
+    fn show_status_for(&mut self, message: String, duration: Duration) {
+        self.status_expiry = Some((Instant::now() + duration, message.clone()));
+        self.status = Some(message);
+    }
+
+    fn exit(&mut self) {
+        self.exit = true;
+    }
+}
+
+/// how many CPU columns fit in one row of `width` characters, at least 1
+fn cpu_columns_for_width(width: u16, cpus: usize, config: CpuConfig) -> usize {
+    let fitting = usize::from(width / config.min_column_width.max(1));
+
+    let columns = cpus.min(fitting).max(1);
+    match config.max_columns {
+        Some(max) => columns.min(max.max(1)),
+        None => columns,
+    }
+}
+
+fn memory_used_pct(memory: &snapshot::MemorySnapshot) -> f64 {
+    if memory.total == 0 {
+        0.0
+    } else {
+        memory.used as f64 / memory.total as f64 * 100.0
+    }
+}
+
+/// inode column text, e.g. `12.5% (1.2M/9.8M)`
+fn inode_usage(partition: &DiskStats) -> String {
+    match (partition.inodes, partition.inodes_used_pct()) {
+        (Some((used, total)), Some(pct)) => {
+            format!(
+                "Inodes: {pct:.1}% ({}/{})",
+                format_count(used),
+                format_count(total)
+            )
+        }
+        _ => "N/A".to_string(),
+    }
+}
+
+/// the options `partition` is mounted with, read-only highlighted since it may be unexpected
+fn render_mount_options(frame: &mut Frame, partition: &DiskStats, area: Rect) {
+    let options = &partition.mount_options;
+
+    let mut spans = vec![Span::from("Mount options: ")];
+    if options.is_empty() {
+        spans.push(Span::from("N/A").dim());
+    }
+    for (index, option) in options.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::from(", "));
+        }
+        let span = Span::from(option.label().to_string());
+        spans.push(if *option == MountOption::ReadOnly {
+            span.yellow().bold()
+        } else {
+            span
+        });
+    }
+
+    let block = Block::new()
+        .title(partition.mount_point.display().to_string())
+        .borders(Borders::ALL);
+    frame.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+}
+
+/// a bordered `title` block with a single "not available" line, for capabilities the host lacks
+fn render_unavailable(frame: &mut Frame, area: Rect, title: &str) {
+    let notice = Paragraph::new("not available on this system")
+        .dim()
+        .block(Block::new().title(title).borders(Borders::ALL));
+
+    frame.render_widget(
+        notice,
+        Rect {
+            height: area.height.min(3),
+            ..area
+        },
+    );
+}
+
+/// formats a count with a metric suffix, e.g. `1.2M`
+fn format_count(count: u64) -> String {
+    match count {
+        0..=9_999 => count.to_string(),
+        10_000..=999_999 => format!("{:.1}K", count as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.1}M", count as f64 / 1e6),
+        _ => format!("{:.1}G", count as f64 / 1e9),
+    }
+}
+
+fn matches_filter(filter: &str, text: &str) -> bool {
+    filter.is_empty() || text.to_lowercase().contains(&filter.to_lowercase())
+}
+
+fn partition_matches(filter: &str, partition: &DiskStats) -> bool {
+    matches_filter(filter, &partition.name)
+        || matches_filter(filter, &partition.mount_point.to_string_lossy())
+}
+
+/// moves the selection of a table with `len` rows by `offset`, clamped to the table
+fn select_row(state: &mut TableState, len: usize, offset: isize) {
+    if len == 0 {
+        return;
+    }
+
+    let current = state.selected().unwrap_or(0);
+    let next = current.saturating_add_signed(offset).min(len - 1);
+
+    state.select(Some(next));
+}
+
+/// formats the RAPL power readings, `None` if no domain is available
+#[cfg(feature = "rapl")]
+fn rapl_line(readings: &[snapshot::PowerReading]) -> Option<String> {
+    if readings.iter().all(|reading| reading.watts.is_none()) {
+        return None;
+    }
+
+    let parts: Vec<String> = readings
+        .iter()
+        .map(|reading| match reading.watts {
+            Some(watts) => format!("{}: {watts:.1} W", reading.label),
+            None => format!("{}: -", reading.label),
+        })
+        .collect();
+
+    Some(parts.join(" | "))
+}
+
+fn interface_detail_lines(details: &InterfaceDetails) -> Vec<Line<'static>> {
+    fn join<T: ToString>(addrs: &[T]) -> String {
+        if addrs.is_empty() {
+            return "-".to_string();
+        }
+
+        addrs
+            .iter()
+            .map(|addr| addr.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    let mtu = details.mtu.map_or("-".to_string(), |mtu| mtu.to_string());
+    let speed = details
+        .speed
+        .map_or("-".to_string(), |speed| format!("{speed} Mb/s"));
+    let duplex = details.duplex.clone().unwrap_or("-".to_string());
+
+    vec![
+        Line::from(format!("IPv4: {}", join(&details.ipv4))),
+        Line::from(format!("IPv6: {}", join(&details.ipv6))),
+        Line::from(format!("MTU: {mtu} | Speed: {speed} | Duplex: {duplex}")),
+    ]
+}
+
+fn main() -> io::Result<()> {
+    let mut timings = StartupTimings::default();
+    let cli = Cli::parse();
+    if cli.watchdog {
+        std::process::exit(watchdog::run()?);
+    }
+    if cli.dry_run {
+        std::process::exit(dry_run::run(&cli));
+    }
+
+    if cli.once {
+        let snapshot = worker::snapshot_once();
+        if snapshot.cpus.is_empty() || snapshot.memory.total == 0 {
+            eprintln!("could not read system information");
+            std::process::exit(1);
+        }
+
+        println!("{}", once::render(&cli.once_format, &snapshot));
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    if cli.dump_config {
+        print!("{}", config.to_toml().map_err(io::Error::other)?);
+        return Ok(());
+    }
+
+    timings.mark(Phase::ConfigLoad);
+    let keyboard_enhancement = tui::supports_keyboard_enhancement();
+    timings.mark(Phase::TerminalQuery);
+    let mut app = App::new(&cli, config, keyboard_enhancement, &mut timings)?;
+    if let Some(addr) = cli.web {
+        web::spawn(addr, app.worker.snapshot.clone())?;
+    }
+    if let Some(addr) = cli.api {
+        api::spawn(addr, app.worker.snapshot.clone())?;
+    }
+    if let Some(addr) = cli.ws {
+        ws::spawn(addr, app.worker.subscribe(), cli.ws_log.as_deref())?;
+    }
+
+    let tick_rate = Duration::from_millis(250);
+
+    if cli.verbose {
+        eprint!("{}", timings.report());
+    }
+
+    app.refresh_addresses();
+
+    let mut terminal = tui::init()?;
+    timings.mark(Phase::TuiInit);
+    app.startup_timings = Some(timings);
+
+    let app_result = app.run(&mut terminal, tick_rate);
+    tui::restore()?;
+    app_result
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+
+    /// a default app showing a fixed snapshot, without a refresh worker or anything
+    /// read from the host
+    fn test_app() -> App {
+        let cli = Cli::parse_from(["sys-tui"]);
+        let snapshot = SystemSnapshot {
+            hostname: "test-host".to_string(),
+            cpus: (0..4)
+                .map(|core| CpuSnapshot {
+                    name: format!("cpu{core}"),
+                    usage: 25.0,
+                    ..CpuSnapshot::default()
+                })
+                .collect(),
+            processes: [(1, "init", false), (2, "kthreadd", true)]
+                .into_iter()
+                .map(|(pid, name, kernel_thread)| ProcessStats {
+                    pid,
+                    name: name.to_string(),
+                    cpu_usage: 1.0,
+                    memory: 1 << 20,
+                    voluntary_switches: 0.0,
+                    involuntary_switches: 0.0,
+                    kernel_thread,
+                    cpu_affinity: "0-3".to_string(),
+                    start_time: 0,
+                    uid: Some(0),
+                    container: None,
+                })
+                .collect(),
+            ..SystemSnapshot::default()
+        };
+        App::with_snapshot(
+            &cli,
+            Config::default(),
+            Worker::fixed(snapshot.clone()),
+            snapshot,
+        )
+    }
+
+    /// the text of one frame of `app` drawn at `width` x `height`, row by row
+    fn render(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("test backend");
+        terminal
+            .draw(|frame| app.render_frame(frame))
+            .expect("frame is drawn");
+
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer.get(x, y).symbol()).collect())
+            .collect()
+    }
+
+    fn draw(width: u16, height: u16) -> String {
+        render(&mut test_app(), width, height).concat()
+    }
+
+    #[test]
+    fn warns_when_the_terminal_is_below_the_minimum_size() {
+        assert!(draw(79, 23).contains("Terminal too small"));
+    }
+
+    #[test]
+    fn does_not_warn_at_the_minimum_size() {
+        assert!(!draw(80, 24).contains("Terminal too small"));
+    }
+
+    /// a key bound on the Processes tab changes what is drawn, but the reload key arm
+    /// comes first in `handle_key_event` and would take it over
+    #[test]
+    fn reserved_keys_cover_every_processes_tab_binding() {
+        let mut app = test_app();
+        app.tab = Tab::Processes;
+        app.process_state.select(Some(0));
+        // a missing `o` must not open a real terminal
+        app.process_config.drill_terminal = Some("true".to_string());
+        // the header holds the clock, which may tick between frames
+        let body = |app: &mut App| render(app, 120, 40).split_off(1);
+        let before = body(&mut app);
+
+        for c in (' '..='~').filter(|c| !config::RESERVED_KEYS.contains(*c)) {
+            app.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+
+            assert!(
+                !app.exit && !app.title_active && body(&mut app) == before,
+                "{c:?} is bound on the Processes tab but missing from RESERVED_KEYS"
+            );
+        }
+    }
 }
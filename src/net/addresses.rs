@@ -0,0 +1,58 @@
+//! IP addresses of all interfaces, for the address column of the network table
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr},
+};
+
+/// addresses by interface name, in the order the OS lists them
+#[cfg(unix)]
+pub fn read_interface_addresses() -> HashMap<String, Vec<IpAddr>> {
+    let mut addresses: HashMap<String, Vec<IpAddr>> = HashMap::new();
+    let Ok(ifaddrs) = nix::ifaddrs::getifaddrs() else {
+        return addresses;
+    };
+
+    for ifaddr in ifaddrs {
+        let Some(address) = ifaddr.address else {
+            continue;
+        };
+
+        let ip = if let Some(v4) = address.as_sockaddr_in() {
+            IpAddr::V4(v4.ip())
+        } else if let Some(v6) = address.as_sockaddr_in6() {
+            IpAddr::V6(v6.ip())
+        } else {
+            continue;
+        };
+        addresses.entry(ifaddr.interface_name).or_default().push(ip);
+    }
+
+    addresses
+}
+
+/// addresses are only read through getifaddrs, so other platforms list none
+#[cfg(not(unix))]
+pub fn read_interface_addresses() -> HashMap<String, Vec<IpAddr>> {
+    HashMap::new()
+}
+
+/// the address to show for an interface: the first IPv4, else an IPv6 one
+/// where link-local addresses come last
+pub fn primary_address(addresses: &[IpAddr]) -> Option<IpAddr> {
+    addresses
+        .iter()
+        .find(|address| address.is_ipv4())
+        .or_else(|| {
+            addresses
+                .iter()
+                .find(|address| matches!(address, IpAddr::V6(v6) if !is_link_local(v6)))
+        })
+        .or_else(|| addresses.first())
+        .copied()
+}
+
+/// `fe80::/10`
+fn is_link_local(address: &Ipv6Addr) -> bool {
+    address.segments()[0] & 0xffc0 == 0xfe80
+}
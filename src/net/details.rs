@@ -0,0 +1,89 @@
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use super::addresses::read_interface_addresses;
+
+/// Static-ish details about a single network interface
+#[derive(Debug, Default, Clone)]
+pub struct InterfaceDetails {
+    pub name: String,
+    pub ipv4: Vec<Ipv4Addr>,
+    pub ipv6: Vec<Ipv6Addr>,
+    pub mtu: Option<u32>,
+    /// link speed in Mb/s, `None` when the driver does not report it
+    pub speed: Option<u32>,
+    pub duplex: Option<String>,
+}
+
+impl InterfaceDetails {
+    /// reads addresses, MTU, link speed and duplex for the interface `name`
+    pub fn read(name: &str) -> Result<InterfaceDetails, io::Error> {
+        let mut details = InterfaceDetails {
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        read_addresses(&mut details);
+        read_link(&mut details)?;
+
+        Ok(details)
+    }
+}
+
+fn read_addresses(details: &mut InterfaceDetails) {
+    let addresses = read_interface_addresses()
+        .remove(&details.name)
+        .unwrap_or_default();
+
+    for address in addresses {
+        match address {
+            IpAddr::V4(v4) => details.ipv4.push(v4),
+            IpAddr::V6(v6) => details.ipv6.push(v6),
+        }
+    }
+}
+
+/// the MTU of the interface `name`, `Ok(None)` where the platform does not report it
+///
+/// The mtu file always exists, so an error means the interface is gone.
+#[cfg(target_os = "linux")]
+pub fn read_mtu(name: &str) -> io::Result<Option<u32>> {
+    let mtu = std::fs::read_to_string(
+        std::path::Path::new("/sys/class/net")
+            .join(name)
+            .join("mtu"),
+    )?;
+    Ok(mtu.trim().parse().ok())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_mtu(_name: &str) -> io::Result<Option<u32>> {
+    Ok(None)
+}
+
+#[cfg(target_os = "linux")]
+fn read_link(details: &mut InterfaceDetails) -> io::Result<()> {
+    let dir = std::path::Path::new("/sys/class/net").join(&details.name);
+
+    details.mtu = read_mtu(&details.name)?;
+
+    // speed and duplex return EINVAL for virtual or down links
+    details.speed = std::fs::read_to_string(dir.join("speed"))
+        .ok()
+        .and_then(|speed| speed.trim().parse::<i64>().ok())
+        .and_then(|speed| u32::try_from(speed).ok());
+    details.duplex = std::fs::read_to_string(dir.join("duplex"))
+        .ok()
+        .map(|duplex| duplex.trim().to_string())
+        .filter(|duplex| !duplex.is_empty() && duplex != "unknown");
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_link(details: &mut InterfaceDetails) -> io::Result<()> {
+    details.mtu = read_mtu(&details.name)?;
+    Ok(())
+}
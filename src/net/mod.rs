@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use sysinfo::Networks;
+
+pub mod addresses;
+pub mod details;
+pub mod mtu;
+
+/// Throughput figures for one interface over the last refresh interval
+#[derive(Debug, Clone, Serialize)]
+pub struct InterfaceStats {
+    pub name: String,
+    pub rx_bps: f64,
+    pub tx_bps: f64,
+    pub total_rx: u64,
+    pub total_tx: u64,
+}
+
+/// collects per-interface throughput, sorted by interface name
+pub fn collect(networks: &Networks, elapsed: Duration) -> Vec<InterfaceStats> {
+    let secs = elapsed.as_secs_f64().max(f64::EPSILON);
+
+    let mut stats: Vec<InterfaceStats> = networks
+        .list()
+        .iter()
+        .map(|(name, data)| InterfaceStats {
+            name: name.clone(),
+            rx_bps: data.received() as f64 / secs,
+            tx_bps: data.transmitted() as f64 / secs,
+            total_rx: data.total_received(),
+            total_tx: data.total_transmitted(),
+        })
+        .collect();
+
+    stats.sort_by(|a, b| a.name.cmp(&b.name));
+    stats
+}
@@ -0,0 +1,91 @@
+//! MTU mismatches between interfaces, which break jumbo frames along the way
+
+use std::collections::HashMap;
+
+use super::details::read_mtu;
+
+/// The MTU Ethernet uses without jumbo frames
+const STANDARD_MTU: u32 = 1500;
+
+/// A warning about the MTU of `interface`, which is marked with `⚠`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtuWarning {
+    pub interface: String,
+    pub message: String,
+}
+
+/// the MTUs of the interfaces `names`, leaving out those without one
+pub fn read_mtus<'a>(names: impl IntoIterator<Item = &'a str>) -> HashMap<String, u32> {
+    names
+        .into_iter()
+        .filter_map(|name| Some((name.to_string(), read_mtu(name).ok().flatten()?)))
+        .collect()
+}
+
+/// the interface of the IPv4 default route
+#[cfg(target_os = "linux")]
+pub fn default_route_interface() -> Option<String> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let interface = fields.next()?;
+        (fields.next()? == "00000000").then(|| interface.to_string())
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn default_route_interface() -> Option<String> {
+    None
+}
+
+/// warnings for interfaces whose MTU differs from the others in `mtus`, or that use
+/// jumbo frames while the default route interface `gateway` does not
+///
+/// `mtus` should only hold active interfaces, without loopback.
+pub fn mtu_warnings(mtus: &HashMap<String, u32>, gateway: Option<&str>) -> Vec<MtuWarning> {
+    let mut interfaces: Vec<(&String, u32)> = mtus.iter().map(|(name, mtu)| (name, *mtu)).collect();
+    interfaces.sort();
+
+    let mut warnings = vec![];
+
+    let highest = interfaces
+        .iter()
+        .map(|(_, mtu)| *mtu)
+        .max()
+        .unwrap_or_default();
+    if interfaces.iter().any(|(_, mtu)| *mtu != highest) {
+        let listing = interfaces
+            .iter()
+            .map(|(name, mtu)| format!("{name}={mtu}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        for (name, mtu) in &interfaces {
+            if *mtu < highest {
+                warnings.push(MtuWarning {
+                    interface: name.to_string(),
+                    message: format!("MTU mismatch: {listing}"),
+                });
+            }
+        }
+    }
+
+    if let Some(gateway) = gateway {
+        if mtus.get(gateway) == Some(&STANDARD_MTU) {
+            for (name, mtu) in &interfaces {
+                if *mtu > STANDARD_MTU {
+                    warnings.push(MtuWarning {
+                        interface: name.to_string(),
+                        message: format!(
+                            "{name} uses MTU {mtu} but the default route via {gateway} has \
+                             {STANDARD_MTU}, jumbo frames may not survive the path"
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    warnings
+}
@@ -0,0 +1,109 @@
+//! Write endurance of NVMe drives, from sysfs and `nvme smart-log`
+
+use std::{
+    collections::HashMap,
+    fs,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+/// How long a reading is reused; `nvme smart-log` is too slow to run every refresh
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Bytes per "data unit" in the SMART log, as defined by the NVMe spec
+const DATA_UNIT_BYTES: f64 = 512_000.0;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct NvmeEndurance {
+    /// rated write endurance left, in percent
+    pub remaining_pct: f64,
+}
+
+/// Endurance readings by namespace, refreshed every [`CACHE_TTL`]
+#[derive(Debug, Default)]
+pub struct EnduranceCache {
+    entries: HashMap<String, (Instant, Option<NvmeEndurance>)>,
+}
+
+impl EnduranceCache {
+    /// the endurance of the drive holding `device` (e.g. `/dev/nvme0n1p2`), `None` for non-NVMe devices
+    pub fn get(&mut self, device: &str) -> Option<NvmeEndurance> {
+        let namespace = namespace(device)?;
+
+        // failed reads are cached too, so a missing `nvme` binary is not spawned every refresh
+        match self.entries.get(&namespace) {
+            Some((read_at, endurance)) if read_at.elapsed() < CACHE_TTL => *endurance,
+            _ => {
+                let endurance = read_nvme_endurance(&namespace);
+                self.entries.insert(namespace, (Instant::now(), endurance));
+                endurance
+            }
+        }
+    }
+}
+
+/// estimates the endurance left on an NVMe namespace such as `nvme0n1`
+///
+/// With a rated capacity in `/sys/block/<namespace>/nvme/nvm_capacity` this is
+/// `(rated - written) / rated`, using the lifetime bytes written from the SMART log.
+/// Most kernels do not expose a rating, in which case the drive's own
+/// `percentage_used` estimate is used instead.
+pub fn read_nvme_endurance(device: &str) -> Option<NvmeEndurance> {
+    let rated = fs::read_to_string(format!("/sys/block/{device}/nvme/nvm_capacity"))
+        .ok()
+        .and_then(|contents| contents.trim().parse::<f64>().ok())
+        .filter(|rated| *rated > 0.0);
+
+    let output = Command::new("nvme")
+        .args([
+            "smart-log",
+            &format!("/dev/{device}"),
+            "--output-format=json",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+
+    let remaining_pct = match (rated, smart_number(&log, &["data_units_written"])) {
+        (Some(rated), Some(units)) => (rated - units * DATA_UNIT_BYTES) / rated * 100.0,
+        _ => 100.0 - smart_number(&log, &["percentage_used", "percent_used"])?,
+    };
+
+    Some(NvmeEndurance {
+        remaining_pct: remaining_pct.clamp(0.0, 100.0),
+    })
+}
+
+/// the first of `keys` present in the SMART log; nvme-cli prints large counters as strings
+fn smart_number(log: &serde_json::Value, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|key| match log.get(key)? {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::String(string) => string.parse().ok(),
+        _ => None,
+    })
+}
+
+/// `/dev/nvme0n1p2` -> `nvme0n1`
+fn namespace(device: &str) -> Option<String> {
+    let name = device.strip_prefix("/dev/").unwrap_or(device);
+    let rest = name.strip_prefix("nvme")?;
+
+    let controller_len = rest.find(|c: char| !c.is_ascii_digit())?;
+    let after_n = rest[controller_len..].strip_prefix('n')?;
+    let namespace_len = after_n
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(after_n.len());
+    if controller_len == 0 || namespace_len == 0 {
+        return None;
+    }
+
+    Some(format!(
+        "nvme{}",
+        &rest[..controller_len + 1 + namespace_len]
+    ))
+}
@@ -0,0 +1,38 @@
+//! `--once`: print a single line built from one snapshot, for status bars
+
+use crate::{snapshot::SystemSnapshot, util::format_uptime};
+
+pub const DEFAULT_TEMPLATE: &str =
+    "{hostname} | CPU: {cpu_avg}% | MEM: {mem_used_gb}/{mem_total_gb} GiB";
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// replaces the `{placeholder}`s of `template`; unknown placeholders are kept as they are
+pub fn render(template: &str, snapshot: &SystemSnapshot) -> String {
+    [
+        ("{cpu_avg}", format!("{:.0}", snapshot.mean_cpu_usage())),
+        (
+            "{mem_used_gb}",
+            format!("{:.1}", snapshot.memory.used as f64 / GIB),
+        ),
+        (
+            "{mem_total_gb}",
+            format!("{:.1}", snapshot.memory.total as f64 / GIB),
+        ),
+        ("{mem_pct}", format!("{:.0}", mem_pct(snapshot))),
+        ("{uptime}", format_uptime(snapshot.uptime_secs)),
+        ("{hostname}", snapshot.hostname.clone()),
+    ]
+    .iter()
+    .fold(template.to_string(), |line, (placeholder, value)| {
+        line.replace(placeholder, value)
+    })
+}
+
+fn mem_pct(snapshot: &SystemSnapshot) -> f64 {
+    if snapshot.memory.total == 0 {
+        return 0.0;
+    }
+
+    snapshot.memory.used as f64 / snapshot.memory.total as f64 * 100.0
+}
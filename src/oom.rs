@@ -0,0 +1,131 @@
+//! Detection of kernel OOM kills from the kernel log
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Seek, SeekFrom},
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+use chrono::{DateTime, Local};
+
+/// An OOM kill reported by the kernel
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    pub name: String,
+    pub pid: u32,
+    pub time: DateTime<Local>,
+}
+
+impl AlertEvent {
+    pub fn banner(&self) -> String {
+        format!(
+            "OOM KILL: {} (PID {}) at {}",
+            self.name,
+            self.pid,
+            self.time.format("%H:%M:%S")
+        )
+    }
+}
+
+/// watches the kernel log for OOM kills in a background thread
+///
+/// Needs CAP_SYSLOG (or `kernel.dmesg_restrict = 0`); without access the
+/// receiver simply never yields anything.
+pub fn spawn() -> Receiver<AlertEvent> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || match File::open("/dev/kmsg") {
+        Ok(kmsg) => watch_kmsg(kmsg, &tx),
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {}
+        Err(_) => watch_dmesg(&tx),
+    });
+
+    rx
+}
+
+fn watch_kmsg(mut kmsg: File, tx: &Sender<AlertEvent>) {
+    // only report kills that happen from now on
+    if kmsg.seek(SeekFrom::End(0)).is_err() {
+        return;
+    }
+
+    let mut reader = BufReader::new(kmsg);
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) => return,
+            Ok(_) => {
+                // records look like `<prio>,<seq>,<usec>,<flags>;<message>`
+                let message = line.split_once(';').map_or(line.as_str(), |(_, msg)| msg);
+                if !report(message, tx) {
+                    return;
+                }
+            }
+            // the record we were about to read was overwritten, carry on with the next one
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {}
+            Err(_) => return,
+        }
+    }
+}
+
+fn watch_dmesg(tx: &Sender<AlertEvent>) {
+    // like --follow, but without replaying the existing ring buffer
+    let Ok(mut child) = Command::new("dmesg")
+        .arg("--follow-new")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return;
+    };
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if !report(&line, tx) {
+                break;
+            }
+        }
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// sends an event if `message` reports an OOM kill, returning `false` once nobody listens
+fn report(message: &str, tx: &Sender<AlertEvent>) -> bool {
+    let Some((name, pid)) = parse_victim(message) else {
+        return true;
+    };
+
+    tx.send(AlertEvent {
+        name,
+        pid,
+        time: Local::now(),
+    })
+    .is_ok()
+}
+
+/// extracts the victim from `Out of memory: Kill(ed) process <pid> (<name>) ...`
+///
+/// `oom_kill_process` stack frames are matched too, but carry no victim and
+/// are therefore only reported if the line also names one.
+fn parse_victim(message: &str) -> Option<(String, u32)> {
+    if !message.contains("Out of memory") && !message.contains("oom_kill_process") {
+        return None;
+    }
+
+    let (_, rest) = message
+        .split_once("Killed process ")
+        .or_else(|| message.split_once("Kill process "))?;
+    let (pid, rest) = rest.split_once(' ')?;
+    let name = rest.strip_prefix('(')?.split_once(')')?.0;
+
+    Some((name.to_string(), pid.parse().ok()?))
+}
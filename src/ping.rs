@@ -0,0 +1,154 @@
+//! `--ping`: background RTT and packet loss measurement against one host
+
+use std::{
+    collections::VecDeque,
+    net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use surge_ping::{Client, Config, PingIdentifier, PingSequence, Pinger, ICMP};
+
+const PROBE_INTERVAL: Duration = Duration::from_secs(1);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+/// probes the loss rate is computed over
+const WINDOW: usize = 60;
+/// port probed when ICMP sockets are not permitted
+const TCP_PORT: u16 = 443;
+
+/// The latest results, written by the ping thread and read by the refresh worker
+pub type SharedPing = Arc<Mutex<PingStats>>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeMethod {
+    #[default]
+    Icmp,
+    /// TCP connects to port 443, used without permission for ICMP sockets
+    Tcp,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PingStats {
+    pub host: String,
+    pub method: ProbeMethod,
+    /// round trip time of the last answered probe in milliseconds
+    pub rtt_ms: Option<f64>,
+    /// unanswered probes of the last minute in percent
+    pub loss_pct: f64,
+    /// why probing is not possible, e.g. an unresolvable host
+    pub error: Option<String>,
+}
+
+/// probes `host` once per second in a background thread
+pub fn spawn(host: String) -> SharedPing {
+    let shared = Arc::new(Mutex::new(PingStats {
+        host: host.clone(),
+        ..Default::default()
+    }));
+
+    let stats = Arc::clone(&shared);
+    thread::spawn(move || {
+        let result = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| err.to_string())
+            .and_then(|runtime| runtime.block_on(run(&host, &stats)));
+
+        if let (Err(err), Ok(mut stats)) = (result, stats.lock()) {
+            stats.error = Some(err);
+        }
+    });
+
+    shared
+}
+
+async fn run(host: &str, stats: &SharedPing) -> Result<(), String> {
+    let addr = resolve(host).ok_or_else(|| format!("cannot resolve {host}"))?;
+
+    let config = match addr {
+        IpAddr::V4(_) => Config::default(),
+        IpAddr::V6(_) => Config::builder().kind(ICMP::V6).build(),
+    };
+
+    // unprivileged ICMP sockets need CAP_NET_RAW or a matching net.ipv4.ping_group_range
+    let client = Client::new(&config).ok();
+    let mut pinger = match &client {
+        Some(client) => {
+            let mut pinger = client
+                .pinger(addr, PingIdentifier(std::process::id() as u16))
+                .await;
+            pinger.timeout(PROBE_TIMEOUT);
+            Some(pinger)
+        }
+        None => None,
+    };
+
+    let method = if pinger.is_some() {
+        ProbeMethod::Icmp
+    } else {
+        ProbeMethod::Tcp
+    };
+
+    let mut window: VecDeque<Option<Duration>> = VecDeque::with_capacity(WINDOW);
+    let mut sequence: u16 = 0;
+
+    loop {
+        let started = Instant::now();
+        let rtt = match &mut pinger {
+            Some(pinger) => probe_icmp(pinger, sequence).await,
+            None => {
+                let target = SocketAddr::new(addr, TCP_PORT);
+                tokio::task::spawn_blocking(move || probe_tcp(target))
+                    .await
+                    .ok()
+                    .flatten()
+            }
+        };
+        sequence = sequence.wrapping_add(1);
+
+        if window.len() == WINDOW {
+            window.pop_front();
+        }
+        window.push_back(rtt);
+
+        if let Ok(mut stats) = stats.lock() {
+            let lost = window.iter().filter(|rtt| rtt.is_none()).count();
+
+            stats.method = method;
+            stats.loss_pct = lost as f64 / window.len() as f64 * 100.0;
+            if let Some(rtt) = rtt {
+                stats.rtt_ms = Some(rtt.as_secs_f64() * 1000.0);
+            }
+        }
+
+        tokio::time::sleep(PROBE_INTERVAL.saturating_sub(started.elapsed())).await;
+    }
+}
+
+fn resolve(host: &str) -> Option<IpAddr> {
+    if let Ok(ip) = host.parse() {
+        return Some(ip);
+    }
+
+    Some((host, 0).to_socket_addrs().ok()?.next()?.ip())
+}
+
+async fn probe_icmp(pinger: &mut Pinger, sequence: u16) -> Option<Duration> {
+    let (_, rtt) = pinger.ping(PingSequence(sequence), &[0; 8]).await.ok()?;
+
+    Some(rtt)
+}
+
+/// a refused connection still proves the host answered, so only timeouts count as loss
+fn probe_tcp(target: SocketAddr) -> Option<Duration> {
+    let started = Instant::now();
+
+    match TcpStream::connect_timeout(&target, PROBE_TIMEOUT) {
+        Ok(_) => Some(started.elapsed()),
+        Err(err) if err.kind() == std::io::ErrorKind::ConnectionRefused => Some(started.elapsed()),
+        Err(_) => None,
+    }
+}
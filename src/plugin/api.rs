@@ -0,0 +1,49 @@
+//! Types shared between sys-tui and widget plugins
+//!
+//! Plugins include this file with `#[path]` and must be built with the same
+//! compiler and `ratatui` version as sys-tui, since trait objects cross the
+//! library boundary.
+
+use ratatui::{buffer::Buffer, layout::Rect};
+
+/// Name of the function every plugin exports
+pub const REGISTER_SYMBOL: &[u8] = b"sys_tui_register_widget";
+
+/// Signature of [`REGISTER_SYMBOL`]
+pub type RegisterFn = unsafe extern "C" fn(registry: *mut WidgetRegistry);
+
+/// A widget provided by a plugin, shown as an extra tab
+pub trait PluginWidget: Send {
+    /// the tab title
+    fn name(&self) -> &str;
+
+    /// keys handled by the widget, shown in the status bar
+    fn key_hint(&self) -> &str;
+
+    /// called once per refresh tick
+    fn refresh(&mut self);
+
+    fn render(&self, area: Rect, buf: &mut Buffer);
+}
+
+/// Collects the widgets registered by plugins
+#[derive(Default)]
+pub struct WidgetRegistry {
+    widgets: Vec<Box<dyn PluginWidget>>,
+}
+
+impl WidgetRegistry {
+    // only called from plugins
+    #[allow(dead_code)]
+    pub fn register(&mut self, widget: Box<dyn PluginWidget>) {
+        self.widgets.push(widget);
+    }
+
+    pub fn widgets(&self) -> &[Box<dyn PluginWidget>] {
+        &self.widgets
+    }
+
+    pub fn widgets_mut(&mut self) -> &mut [Box<dyn PluginWidget>] {
+        &mut self.widgets
+    }
+}
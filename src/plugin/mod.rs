@@ -0,0 +1,86 @@
+//! Loading of widget plugins from `--plugin-dir`
+
+use std::{
+    ffi::OsStr,
+    io,
+    path::{Path, PathBuf},
+};
+
+use libloading::{Library, Symbol};
+
+pub mod api;
+
+use api::{PluginWidget, RegisterFn, WidgetRegistry, REGISTER_SYMBOL};
+
+/// Widgets loaded from shared libraries, together with the libraries backing them
+pub struct Plugins {
+    // declared before `libraries` so the widgets are dropped while their code is still loaded
+    registry: WidgetRegistry,
+    libraries: Vec<Library>,
+}
+
+impl std::fmt::Debug for Plugins {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Plugins")
+            .field("widgets", &self.registry.widgets().len())
+            .field("libraries", &self.libraries.len())
+            .finish()
+    }
+}
+
+impl Plugins {
+    pub fn none() -> Plugins {
+        Plugins {
+            registry: WidgetRegistry::default(),
+            libraries: vec![],
+        }
+    }
+
+    /// loads every `.so` / `.dylib` in `dir`, returning an error message per library that failed
+    pub fn load_dir(dir: &Path) -> io::Result<(Plugins, Vec<String>)> {
+        let mut plugins = Plugins::none();
+        let mut errors = vec![];
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| {
+                matches!(
+                    path.extension().and_then(OsStr::to_str),
+                    Some("so" | "dylib")
+                )
+            })
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            if let Err(err) = plugins.load(&path) {
+                errors.push(format!("{}: {err}", path.display()));
+            }
+        }
+
+        Ok((plugins, errors))
+    }
+
+    fn load(&mut self, path: &Path) -> Result<(), libloading::Error> {
+        // SAFETY: plugins are trusted code the user explicitly pointed us at; the
+        // registration function has the documented `RegisterFn` signature
+        unsafe {
+            let library = Library::new(path)?;
+            let register: Symbol<RegisterFn> = library.get(REGISTER_SYMBOL)?;
+            register(&mut self.registry);
+            self.libraries.push(library);
+        }
+
+        Ok(())
+    }
+
+    pub fn widgets(&self) -> &[Box<dyn PluginWidget>] {
+        self.registry.widgets()
+    }
+
+    pub fn refresh(&mut self) {
+        for widget in self.registry.widgets_mut() {
+            widget.refresh();
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! Per-core CPU time accounting from `/proc/stat`
+//!
+//! Only Linux has this file; elsewhere [`read_cpu_times`] fails and callers
+//! treat steal and iowait as unknown.
+
+use std::{fs, io};
+
+/// Cumulative jiffies of one core, in the column order of `/proc/stat`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+impl CpuTimes {
+    /// jiffies spent between `earlier` and `self`
+    pub fn delta(&self, earlier: &CpuTimes) -> CpuTimes {
+        CpuTimes {
+            user: self.user.saturating_sub(earlier.user),
+            nice: self.nice.saturating_sub(earlier.nice),
+            system: self.system.saturating_sub(earlier.system),
+            idle: self.idle.saturating_sub(earlier.idle),
+            iowait: self.iowait.saturating_sub(earlier.iowait),
+            irq: self.irq.saturating_sub(earlier.irq),
+            softirq: self.softirq.saturating_sub(earlier.softirq),
+            steal: self.steal.saturating_sub(earlier.steal),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+
+    /// share of `field` in the total, in percent
+    pub fn pct(&self, field: u64) -> f32 {
+        match self.total() {
+            0 => 0.0,
+            total => field as f32 / total as f32 * 100.0,
+        }
+    }
+}
+
+/// sum of `times` over all cores
+pub fn combined(times: &[CpuTimes]) -> CpuTimes {
+    times
+        .iter()
+        .fold(CpuTimes::default(), |sum, core| CpuTimes {
+            user: sum.user + core.user,
+            nice: sum.nice + core.nice,
+            system: sum.system + core.system,
+            idle: sum.idle + core.idle,
+            iowait: sum.iowait + core.iowait,
+            irq: sum.irq + core.irq,
+            softirq: sum.softirq + core.softirq,
+            steal: sum.steal + core.steal,
+        })
+}
+
+/// share of nominal CPU time lost to I/O wait, the hypervisor and interrupt handling, in percent
+pub fn cpu_contention(times: &CpuTimes) -> f32 {
+    times.pct(times.iowait)
+        + times.pct(times.steal)
+        + times.pct(times.irq)
+        + times.pct(times.softirq)
+}
+
+/// reads the `cpuN` lines of `/proc/stat`, indexed by core
+pub fn read_cpu_times() -> Result<Vec<CpuTimes>, io::Error> {
+    let contents = fs::read_to_string("/proc/stat")?;
+
+    Ok(contents
+        .lines()
+        .filter(|line| line.starts_with("cpu") && !line.starts_with("cpu "))
+        .map(|line| {
+            let mut fields = line
+                .split_whitespace()
+                .skip(1)
+                .map(|field| field.parse().unwrap_or(0));
+            let mut next = || fields.next().unwrap_or(0);
+
+            CpuTimes {
+                user: next(),
+                nice: next(),
+                system: next(),
+                idle: next(),
+                iowait: next(),
+                irq: next(),
+                softirq: next(),
+                steal: next(),
+            }
+        })
+        .collect())
+}
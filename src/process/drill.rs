@@ -0,0 +1,70 @@
+//! Opening `htop` or `top` for a single process in a new terminal window
+
+use std::{
+    env,
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+};
+
+/// `htop -p <pid>` if htop is installed, `top -p <pid>` otherwise
+pub fn command(pid: u32) -> Vec<String> {
+    let program = if in_path("htop") { "htop" } else { "top" };
+
+    vec![program.to_string(), "-p".to_string(), pid.to_string()]
+}
+
+/// the terminal emulator to start: `configured`, then `$TERMINAL`, then
+/// `$TERM_PROGRAM` if it names a program in `PATH`
+pub fn terminal(configured: Option<&str>) -> Option<String> {
+    if let Some(terminal) = configured.filter(|terminal| !terminal.trim().is_empty()) {
+        return Some(terminal.to_string());
+    }
+    if let Ok(terminal) = env::var("TERMINAL") {
+        if !terminal.trim().is_empty() {
+            return Some(terminal);
+        }
+    }
+
+    // e.g. `WezTerm`, while values like `Apple_Terminal` or `vscode` are not programs
+    env::var("TERM_PROGRAM")
+        .ok()
+        .map(|program| program.to_lowercase())
+        .filter(|program| in_path(program))
+}
+
+/// starts `terminal -e <command>` detached from our own terminal
+///
+/// `terminal` may carry arguments of its own, e.g. `alacritty --class drill`
+pub fn spawn(terminal: &str, command: &[String]) -> std::io::Result<()> {
+    let mut words = terminal.split_whitespace();
+    let program = words.next().unwrap_or(terminal);
+
+    let mut child = Command::new(program);
+    child
+        .args(words)
+        .arg("-e")
+        .args(command)
+        // the TUI owns our terminal, so the new window must not read or write it
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    // its own process group keeps Ctrl+C in our terminal from reaching it
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut child, 0);
+
+    let mut child = child.spawn()?;
+
+    // reaps it once the window is closed, so it does not linger as a zombie
+    thread::spawn(move || child.wait());
+
+    Ok(())
+}
+
+/// whether `program` is a file in one of the `$PATH` directories
+pub fn in_path(program: &str) -> bool {
+    env::var_os("PATH").is_some_and(|path| {
+        env::split_paths(&path).any(|dir| Path::new(&dir).join(program).is_file())
+    })
+}
@@ -0,0 +1,232 @@
+//! Per-process statistics for the Processes tab
+
+use std::{collections::HashMap, time::Duration};
+
+use serde::Serialize;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System, ThreadKind, UpdateKind};
+
+use crate::config::ReloadSignal;
+
+pub mod drill;
+pub mod smaps;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessStats {
+    pub pid: u32,
+    pub name: String,
+    /// usage in percent of one core
+    pub cpu_usage: f32,
+    /// resident memory in bytes
+    pub memory: u64,
+    /// voluntary context switches per second
+    pub voluntary_switches: f64,
+    /// involuntary context switches per second
+    pub involuntary_switches: f64,
+    /// no command line, which is how kernel threads show up
+    pub kernel_thread: bool,
+    /// cores the process may run on, e.g. `0-3`; empty where unknown
+    pub cpu_affinity: String,
+    /// unix timestamp in seconds
+    pub start_time: u64,
+    /// owner, only reported on unix
+    pub uid: Option<u32>,
+    /// name of the Docker container it runs in, only reported on Linux
+    pub container: Option<String>,
+}
+
+/// Remembers the previous counters of every process to turn them into rates
+#[derive(Debug, Default)]
+pub struct ProcessCollector {
+    last_switches: HashMap<u32, (u64, u64)>,
+    #[cfg(target_os = "linux")]
+    containers: crate::docker::ContainerNames,
+}
+
+impl ProcessCollector {
+    /// collects every process (threads excluded), sorted by CPU usage
+    pub fn collect(&mut self, system: &System, elapsed: Duration) -> Vec<ProcessStats> {
+        let mut switches = HashMap::new();
+
+        let mut processes: Vec<ProcessStats> = system
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind() != Some(ThreadKind::Userland))
+            .map(|process| {
+                let pid = process.pid().as_u32();
+                let (voluntary, involuntary, cpu_affinity) = read_status(pid);
+                let (last_voluntary, last_involuntary) = self
+                    .last_switches
+                    .get(&pid)
+                    .copied()
+                    .unwrap_or((voluntary, involuntary));
+                switches.insert(pid, (voluntary, involuntary));
+
+                ProcessStats {
+                    pid,
+                    name: process.name().to_string_lossy().into_owned(),
+                    cpu_usage: process.cpu_usage(),
+                    memory: process.memory(),
+                    voluntary_switches: per_sec(last_voluntary, voluntary, elapsed),
+                    involuntary_switches: per_sec(last_involuntary, involuntary, elapsed),
+                    kernel_thread: process.cmd().is_empty(),
+                    cpu_affinity,
+                    start_time: process.start_time(),
+                    #[cfg(unix)]
+                    uid: process.user_id().map(|uid| **uid),
+                    #[cfg(not(unix))]
+                    uid: None,
+                    #[cfg(target_os = "linux")]
+                    container: self.containers.pid_to_container_name(pid),
+                    #[cfg(not(target_os = "linux"))]
+                    container: None,
+                }
+            })
+            .collect();
+
+        processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage).then(a.pid.cmp(&b.pid)));
+        self.last_switches = switches;
+        #[cfg(target_os = "linux")]
+        self.containers.prune();
+
+        processes
+    }
+}
+
+/// sends `signal` to `pid`
+#[cfg(unix)]
+pub fn send_signal(pid: u32, signal: ReloadSignal) -> std::io::Result<()> {
+    use nix::sys::signal::{self, Signal};
+
+    let signal = match signal {
+        ReloadSignal::Usr1 => Signal::SIGUSR1,
+        ReloadSignal::Usr2 => Signal::SIGUSR2,
+        ReloadSignal::Hup => Signal::SIGHUP,
+        ReloadSignal::Winch => Signal::SIGWINCH,
+    };
+    let pid = nix::unistd::Pid::from_raw(pid as i32);
+
+    signal::kill(pid, signal).map_err(std::io::Error::from)
+}
+
+/// there are no signals to send outside unix
+#[cfg(not(unix))]
+pub fn send_signal(_pid: u32, _signal: ReloadSignal) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "signals are only supported on unix",
+    ))
+}
+
+/// the user we run as, `None` where processes have no numeric owner
+pub fn current_uid() -> Option<u32> {
+    #[cfg(unix)]
+    return Some(nix::unistd::Uid::current().as_raw());
+
+    #[cfg(not(unix))]
+    None
+}
+
+/// reads the environment of `pid` as `KEY=value` strings
+///
+/// `Err` carries a message to show instead, e.g. when not permitted to read it
+pub fn read_environ(pid: u32) -> Result<Vec<String>, String> {
+    let mut system = System::new();
+    let pid = Pid::from_u32(pid);
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        ProcessRefreshKind::new().with_environ(UpdateKind::Always),
+    );
+
+    let Some(process) = system.process(pid) else {
+        return Err("process exited".to_string());
+    };
+
+    let environ: Vec<String> = process
+        .environ()
+        .iter()
+        .map(|var| var.to_string_lossy().into_owned())
+        .collect();
+
+    // sysinfo reports an unreadable environment as an empty one
+    #[cfg(target_os = "linux")]
+    if environ.is_empty() {
+        if let Err(err) = std::fs::File::open(format!("/proc/{pid}/environ")) {
+            if err.kind() == std::io::ErrorKind::PermissionDenied {
+                return Err("Permission denied - run as root to view env".to_string());
+            }
+        }
+    }
+
+    Ok(environ)
+}
+
+/// rate of a monotonic counter between two samples, 0 if the counter went backwards
+pub fn per_sec(before: u64, after: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs == 0.0 {
+        return 0.0;
+    }
+
+    after.saturating_sub(before) as f64 / secs
+}
+
+/// `(voluntary switches, involuntary switches, affinity)` of one process
+#[cfg(target_os = "linux")]
+fn read_status(pid: u32) -> (u64, u64, String) {
+    let status = crate::procfs::read_process_status(pid).unwrap_or_default();
+
+    (
+        status.voluntary_ctxt_switches,
+        status.nonvoluntary_ctxt_switches,
+        status.cpus_allowed_list,
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_status(_pid: u32) -> (u64, u64, String) {
+    (0, 0, String::new())
+}
+
+/// pins every thread of sys-tui to `core`
+#[cfg(target_os = "linux")]
+pub fn pin_self_to_core(core: usize) -> std::io::Result<()> {
+    use nix::{sched::CpuSet, unistd::Pid};
+
+    let mut cpu_set = CpuSet::new();
+    cpu_set.set(core)?;
+
+    // sched_setaffinity only affects one thread, so apply it to each of ours
+    for entry in std::fs::read_dir("/proc/self/task")? {
+        let Some(tid) = entry?.file_name().to_str().and_then(|tid| tid.parse().ok()) else {
+            continue;
+        };
+        nix::sched::sched_setaffinity(Pid::from_raw(tid), &cpu_set)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_self_to_core(_core: usize) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_sec_divides_the_delta_by_the_elapsed_time() {
+        assert_eq!(per_sec(100, 350, Duration::from_millis(2500)), 100.0);
+    }
+
+    #[test]
+    fn per_sec_is_zero_when_the_counter_went_backwards() {
+        assert_eq!(per_sec(350, 100, Duration::from_secs(1)), 0.0);
+    }
+
+    #[test]
+    fn per_sec_is_zero_without_elapsed_time() {
+        assert_eq!(per_sec(100, 350, Duration::ZERO), 0.0);
+    }
+}
@@ -0,0 +1,127 @@
+//! Memory map summary of one process from `/proc/<pid>/smaps`
+
+use std::{fs, io};
+
+/// What a mapping in smaps is backed by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentKind {
+    Heap,
+    Stack,
+    /// no backing file, including kernel pseudo mappings like `[vdso]`
+    Anonymous,
+    FileMapped,
+}
+
+impl SegmentKind {
+    pub const ALL: [SegmentKind; 4] = [
+        SegmentKind::Heap,
+        SegmentKind::Stack,
+        SegmentKind::Anonymous,
+        SegmentKind::FileMapped,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SegmentKind::Heap => "heap",
+            SegmentKind::Stack => "stack",
+            SegmentKind::Anonymous => "anonymous",
+            SegmentKind::FileMapped => "file-mapped",
+        }
+    }
+
+    /// from the pathname column of a mapping header, empty for anonymous mappings
+    fn of_path(path: &str) -> SegmentKind {
+        if path == "[heap]" {
+            SegmentKind::Heap
+        } else if path.starts_with("[stack") {
+            SegmentKind::Stack
+        } else if path.starts_with('/') {
+            SegmentKind::FileMapped
+        } else {
+            SegmentKind::Anonymous
+        }
+    }
+}
+
+/// Resident memory of all mappings of one kind, in bytes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SegmentUsage {
+    pub private_rss: u64,
+    pub shared_rss: u64,
+    /// private and shared dirty pages
+    pub dirty: u64,
+}
+
+/// Resident memory by segment kind, indexed like [`SegmentKind::ALL`]
+#[derive(Debug, Clone, Default)]
+pub struct SmapsSummary {
+    pub segments: [SegmentUsage; 4],
+}
+
+impl SmapsSummary {
+    pub fn get(&self, kind: SegmentKind) -> SegmentUsage {
+        self.segments[kind as usize]
+    }
+
+    /// the sum over all segment kinds
+    pub fn total(&self) -> SegmentUsage {
+        self.segments
+            .iter()
+            .fold(SegmentUsage::default(), |total, usage| SegmentUsage {
+                private_rss: total.private_rss + usage.private_rss,
+                shared_rss: total.shared_rss + usage.shared_rss,
+                dirty: total.dirty + usage.dirty,
+            })
+    }
+}
+
+pub fn read_smaps_summary(pid: u32) -> Result<SmapsSummary, io::Error> {
+    Ok(parse_smaps(&fs::read_to_string(format!(
+        "/proc/{pid}/smaps"
+    ))?))
+}
+
+fn parse_smaps(contents: &str) -> SmapsSummary {
+    let mut summary = SmapsSummary::default();
+    let mut kind = SegmentKind::Anonymous;
+
+    for line in contents.lines() {
+        // mapping headers start with the address range, e.g. `55d0c5a00000-55d0c5a21000 rw-p ...`
+        let first = line.split_whitespace().next().unwrap_or_default();
+        if first.contains('-') && first.bytes().all(|b| b.is_ascii_hexdigit() || b == b'-') {
+            let path = line.split_whitespace().nth(5).unwrap_or_default();
+            kind = SegmentKind::of_path(path);
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let Some(kb) = value
+            .trim()
+            .strip_suffix(" kB")
+            .and_then(|kb| kb.trim().parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let bytes = kb * 1024;
+
+        let usage = &mut summary.segments[kind as usize];
+        match key {
+            "Private_Clean" => usage.private_rss += bytes,
+            "Private_Dirty" => {
+                usage.private_rss += bytes;
+                usage.dirty += bytes;
+            }
+            "Shared_Clean" => usage.shared_rss += bytes,
+            "Shared_Dirty" => {
+                usage.shared_rss += bytes;
+                usage.dirty += bytes;
+            }
+            _ => {}
+        }
+    }
+
+    summary
+}
@@ -0,0 +1,95 @@
+//! Parsers for Linux `/proc` key-value files
+
+use std::{collections::HashMap, fs, io};
+
+/// reads `/proc/meminfo`, with every value converted to bytes
+pub fn read_meminfo() -> io::Result<HashMap<String, u64>> {
+    Ok(parse_meminfo(&fs::read_to_string("/proc/meminfo")?))
+}
+
+/// reads the counters of `/proc/vmstat`, e.g. `pswpin`
+pub fn read_vmstat() -> io::Result<HashMap<String, u64>> {
+    let contents = fs::read_to_string("/proc/vmstat")?;
+
+    Ok(contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse().ok()?))
+        })
+        .collect())
+}
+
+/// total `(read, written)` bytes since boot of every block device in `/proc/diskstats`
+pub fn read_diskstats() -> io::Result<HashMap<String, (u64, u64)>> {
+    // the kernel counts in 512 byte sectors, whatever the device's sector size
+    const SECTOR_SIZE: u64 = 512;
+
+    let contents = fs::read_to_string("/proc/diskstats")?;
+
+    Ok(contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let sectors_read: u64 = fields.get(5)?.parse().ok()?;
+            let sectors_written: u64 = fields.get(9)?.parse().ok()?;
+
+            Some((
+                fields[2].to_string(),
+                (sectors_read * SECTOR_SIZE, sectors_written * SECTOR_SIZE),
+            ))
+        })
+        .collect())
+}
+
+fn parse_meminfo(contents: &str) -> HashMap<String, u64> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let mut parts = value.split_whitespace();
+
+            let amount: u64 = parts.next()?.parse().ok()?;
+            let multiplier = match parts.next() {
+                Some("kB") => 1024,
+                _ => 1,
+            };
+
+            Some((key.to_string(), amount * multiplier))
+        })
+        .collect()
+}
+
+/// The fields of `/proc/<pid>/status` used by the process table
+#[derive(Debug, Clone, Default)]
+pub struct ProcessStatus {
+    pub voluntary_ctxt_switches: u64,
+    pub nonvoluntary_ctxt_switches: u64,
+    /// cores the process may run on, e.g. `0-3,6`
+    pub cpus_allowed_list: String,
+}
+
+pub fn read_process_status(pid: u32) -> io::Result<ProcessStatus> {
+    let contents = fs::read_to_string(format!("/proc/{pid}/status"))?;
+    let mut status = ProcessStatus::default();
+
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match key {
+            "voluntary_ctxt_switches" => {
+                status.voluntary_ctxt_switches = value.parse().unwrap_or(0)
+            }
+            "nonvoluntary_ctxt_switches" => {
+                status.nonvoluntary_ctxt_switches = value.parse().unwrap_or(0)
+            }
+            "Cpus_allowed_list" => status.cpus_allowed_list = value.to_string(),
+            _ => {}
+        }
+    }
+
+    Ok(status)
+}
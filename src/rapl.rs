@@ -0,0 +1,154 @@
+//! CPU power draw estimated from Intel RAPL or AMD energy counters
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use crate::snapshot::PowerReading;
+
+/// Domains shown below the CPU usage bars, with their labels
+const DOMAINS: [(&str, &str); 3] = [
+    ("package", "Package"),
+    ("core", "Core"),
+    ("uncore", "Uncore"),
+];
+
+/// Turns the energy counters of the [`DOMAINS`] into watts between two readings,
+/// so nothing has to sleep to sample them
+#[derive(Debug)]
+pub struct PowerMeter {
+    domains: Vec<DomainMeter>,
+}
+
+#[derive(Debug)]
+struct DomainMeter {
+    label: &'static str,
+    /// `None` if the domain is not exposed
+    counter: Option<EnergyCounter>,
+    /// the previous reading of `counter` and when it was taken
+    last: Option<(Instant, u64)>,
+}
+
+impl Default for PowerMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PowerMeter {
+    /// finds the counters and takes the first reading of each
+    pub fn new() -> Self {
+        let domains = DOMAINS
+            .iter()
+            .map(|(domain, label)| {
+                let counter = find_counter(domain);
+                let last = counter
+                    .as_ref()
+                    .and_then(|counter| Some((Instant::now(), read_u64(&counter.energy)?)));
+
+                DomainMeter {
+                    label,
+                    counter,
+                    last,
+                }
+            })
+            .collect();
+
+        PowerMeter { domains }
+    }
+
+    /// the mean power draw of every domain since the previous reading
+    pub fn read(&mut self) -> Vec<PowerReading> {
+        self.domains
+            .iter_mut()
+            .map(|domain| PowerReading {
+                label: domain.label.to_string(),
+                watts: domain.read_watts(),
+            })
+            .collect()
+    }
+}
+
+impl DomainMeter {
+    fn read_watts(&mut self) -> Option<f64> {
+        let counter = self.counter.as_ref()?;
+        let energy = read_u64(&counter.energy)?;
+        let now = Instant::now();
+        let (last_read, last_energy) = self.last.replace((now, energy))?;
+
+        // the counter wraps around at max_energy_range_uj
+        let delta = if energy >= last_energy {
+            energy - last_energy
+        } else {
+            counter.max_range? - last_energy + energy
+        };
+        let secs = now.duration_since(last_read).as_secs_f64();
+
+        (secs > 0.0).then(|| delta as f64 / 1_000_000.0 / secs)
+    }
+}
+
+/// An energy counter in microjoules
+#[derive(Debug)]
+struct EnergyCounter {
+    energy: PathBuf,
+    max_range: Option<u64>,
+}
+
+fn find_counter(domain: &str) -> Option<EnergyCounter> {
+    find_rapl_counter(domain).or_else(|| {
+        // AMD exposes socket energy through hwmon instead of powercap
+        (domain == "package").then(find_hwmon_counter).flatten()
+    })
+}
+
+/// finds the first RAPL zone whose name matches `domain`, e.g. `package-0` for `package`
+fn find_rapl_counter(domain: &str) -> Option<EnergyCounter> {
+    let mut zones: Vec<PathBuf> = fs::read_dir("/sys/class/powercap")
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("intel-rapl:"))
+        })
+        .collect();
+    zones.sort();
+
+    zones.into_iter().find_map(|zone| {
+        let name = fs::read_to_string(zone.join("name")).ok()?;
+        let name = name.trim();
+
+        if name != domain && !name.starts_with(&format!("{domain}-")) {
+            return None;
+        }
+
+        Some(EnergyCounter {
+            energy: zone.join("energy_uj"),
+            max_range: read_u64(&zone.join("max_energy_range_uj")),
+        })
+    })
+}
+
+fn find_hwmon_counter() -> Option<EnergyCounter> {
+    let mut hwmons: Vec<PathBuf> = fs::read_dir("/sys/class/hwmon")
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect();
+    hwmons.sort();
+
+    hwmons
+        .into_iter()
+        .map(|hwmon| hwmon.join("energy1_input"))
+        .find(|energy| energy.exists())
+        .map(|energy| EnergyCounter {
+            energy,
+            max_range: None,
+        })
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
@@ -0,0 +1,110 @@
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::{
+    disk::DiskStats, dns::DnsResult, net::InterfaceStats, ping::PingStats, process::ProcessStats,
+};
+
+/// The latest snapshot, shared between the refresh worker and the servers
+pub type SharedSnapshot = Arc<Mutex<SystemSnapshot>>;
+
+/// Everything shown by the UI, captured in a single refresh
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SystemSnapshot {
+    /// unix timestamp in seconds
+    pub timestamp: i64,
+    pub hostname: String,
+    pub uptime_secs: u64,
+    /// 1, 5 and 15 minute load average
+    pub load_average: [f64; 3],
+    pub cpus: Vec<CpuSnapshot>,
+    /// iowait + steal + irq + softirq over all cores in percent, only reported on Linux
+    pub cpu_contention: Option<f32>,
+    /// °C by core number, from sensors labelled `Core N`; empty without such sensors
+    pub core_temperatures: Vec<Option<f32>>,
+    pub memory: MemorySnapshot,
+    pub networks: Vec<InterfaceStats>,
+    pub disks: Vec<DiskStats>,
+    /// sorted by CPU usage, highest first
+    pub processes: Vec<ProcessStats>,
+    /// unix timestamp in milliseconds of when `processes` was collected
+    pub processes_updated_ms: i64,
+    /// `--ping` results, if enabled
+    pub ping: Option<PingStats>,
+    /// `--dns-check` results, in the order given
+    pub dns: Vec<DnsResult>,
+    #[cfg(feature = "perf_events")]
+    pub context_switches: Option<ContextSwitches>,
+    #[cfg(feature = "rapl")]
+    pub power: Vec<PowerReading>,
+}
+
+impl SystemSnapshot {
+    /// average usage over all cores in percent
+    pub fn mean_cpu_usage(&self) -> f32 {
+        if self.cpus.is_empty() {
+            return 0.0;
+        }
+
+        self.cpus.iter().map(|cpu| cpu.usage).sum::<f32>() / self.cpus.len() as f32
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CpuSnapshot {
+    pub name: String,
+    /// usage in percent
+    pub usage: f32,
+    /// frequency in MHz
+    pub frequency: u64,
+    /// time taken by the hypervisor in percent, only reported on Linux
+    pub steal_pct: Option<f32>,
+    /// time idle while waiting for I/O in percent, only reported on Linux;
+    /// `usage` does not include it, so the two stack up to at most 100 %
+    pub iowait_pct: Option<f32>,
+}
+
+/// Memory figures in bytes
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemorySnapshot {
+    pub total: u64,
+    pub used: u64,
+    pub total_swap: u64,
+    pub used_swap: u64,
+    /// page cache and buffer split, only reported on Linux
+    pub breakdown: Option<MemoryBreakdown>,
+    /// swap traffic, only reported on Linux
+    pub swap_io: Option<SwapIo>,
+}
+
+/// Pages swapped in and out per second
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SwapIo {
+    pub pages_in: f64,
+    pub pages_out: f64,
+}
+
+/// Physical memory split the way htop does; `used` excludes reclaimable cache
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemoryBreakdown {
+    pub used: u64,
+    pub cached: u64,
+    pub buffers: u64,
+    pub free: u64,
+}
+
+#[cfg(feature = "perf_events")]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ContextSwitches {
+    /// system-wide switches per second, `None` until two samples were taken
+    pub per_sec: Option<f64>,
+    pub top: Vec<crate::syscall_rate::ProcessSwitchRate>,
+}
+
+#[cfg(feature = "rapl")]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PowerReading {
+    pub label: String,
+    pub watts: Option<f64>,
+}
@@ -0,0 +1,40 @@
+//! Parsing of the `startup_macro` config key into synthesized key presses
+
+use ratatui::crossterm::event::KeyCode;
+
+/// parses `macro_str` into keys, returning a warning for every unsupported character
+///
+/// digits switch tabs, `f` toggles fullscreen, `p` pauses, and `/` starts a search
+/// whose text runs until a newline (or the end of the macro) and is submitted with Enter
+pub fn parse(macro_str: &str) -> (Vec<KeyCode>, Vec<String>) {
+    let mut keys = vec![];
+    let mut warnings = vec![];
+    let mut in_search = false;
+
+    for c in macro_str.chars() {
+        if in_search {
+            if c == '\n' || c == '\r' {
+                keys.push(KeyCode::Enter);
+                in_search = false;
+            } else {
+                keys.push(KeyCode::Char(c));
+            }
+            continue;
+        }
+
+        match c {
+            '1'..='9' | 'f' | 'p' => keys.push(KeyCode::Char(c)),
+            '/' => {
+                keys.push(KeyCode::Char('/'));
+                in_search = true;
+            }
+            _ => warnings.push(format!("startup_macro: ignoring unsupported key {c:?}")),
+        }
+    }
+
+    if in_search {
+        keys.push(KeyCode::Enter);
+    }
+
+    (keys, warnings)
+}
@@ -0,0 +1,109 @@
+//! How long each phase of startup took, for the status bar and `--verbose`
+
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+/// The phases of startup, in the order they happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    ConfigLoad,
+    /// asking the terminal whether it supports keyboard enhancement, which waits for
+    /// its reply
+    TerminalQuery,
+    SysinfoInit,
+    FirstCpuRefresh,
+    FirstProcessList,
+    /// includes setting up the app state before the terminal
+    TuiInit,
+    FirstFrame,
+}
+
+impl Phase {
+    fn name(self) -> &'static str {
+        match self {
+            Phase::ConfigLoad => "config load",
+            Phase::TerminalQuery => "terminal query",
+            Phase::SysinfoInit => "sysinfo init",
+            Phase::FirstCpuRefresh => "first CPU refresh",
+            Phase::FirstProcessList => "first process list",
+            Phase::TuiInit => "TUI init",
+            Phase::FirstFrame => "first frame",
+        }
+    }
+
+    /// the phases spent in sysinfo, summed up in [`StartupTimings::summary`]
+    fn is_sysinfo(self) -> bool {
+        matches!(
+            self,
+            Phase::SysinfoInit | Phase::FirstCpuRefresh | Phase::FirstProcessList
+        )
+    }
+}
+
+/// Durations of the startup phases marked so far
+#[derive(Debug, Clone)]
+pub struct StartupTimings {
+    last_mark: Instant,
+    phases: Vec<(Phase, Duration)>,
+}
+
+impl Default for StartupTimings {
+    fn default() -> Self {
+        StartupTimings {
+            last_mark: Instant::now(),
+            phases: vec![],
+        }
+    }
+}
+
+impl StartupTimings {
+    /// records that `phase` ended now; it started at the previous mark
+    pub fn mark(&mut self, phase: Phase) {
+        let now = Instant::now();
+        self.phases.push((phase, now - self.last_mark));
+        self.last_mark = now;
+    }
+
+    fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, duration)| *duration).sum()
+    }
+
+    /// e.g. `Started in 1.23 s (sysinfo: 0.87 s)`
+    pub fn summary(&self) -> String {
+        let sysinfo: Duration = self
+            .phases
+            .iter()
+            .filter(|(phase, _)| phase.is_sysinfo())
+            .map(|(_, duration)| *duration)
+            .sum();
+
+        format!(
+            "Started in {:.2} s (sysinfo: {:.2} s)",
+            self.total().as_secs_f64(),
+            sysinfo.as_secs_f64()
+        )
+    }
+
+    /// one line per phase marked so far, then the total
+    pub fn report(&self) -> String {
+        let mut report = String::from("startup timings:\n");
+        for (phase, duration) in &self.phases {
+            let _ = writeln!(
+                report,
+                "  {:<20}{:>8.3} s",
+                phase.name(),
+                duration.as_secs_f64()
+            );
+        }
+        let _ = writeln!(
+            report,
+            "  {:<20}{:>8.3} s",
+            "total",
+            self.total().as_secs_f64()
+        );
+
+        report
+    }
+}
@@ -0,0 +1,110 @@
+//! Context switch rates, used as an approximation of system-wide syscall pressure
+
+use std::time::Instant;
+
+use serde::Serialize;
+
+use crate::process::ProcessStats;
+
+/// Number of processes shown in the top list
+const TOP_PROCESSES: usize = 5;
+
+/// Context switch rate of a single process
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessSwitchRate {
+    pub pid: u32,
+    pub name: String,
+    pub voluntary: f64,
+    pub involuntary: f64,
+}
+
+#[derive(Debug)]
+pub struct SyscallRate {
+    last_sample: Instant,
+    last_total: Option<u64>,
+    /// system-wide context switches per second, `None` until two samples were taken
+    pub per_sec: Option<f64>,
+    pub top: Vec<ProcessSwitchRate>,
+}
+
+impl Default for SyscallRate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyscallRate {
+    pub fn new() -> Self {
+        SyscallRate {
+            last_sample: Instant::now(),
+            last_total: None,
+            per_sec: None,
+            top: vec![],
+        }
+    }
+
+    /// whether the current platform exposes context switch counters
+    pub fn is_supported() -> bool {
+        cfg!(target_os = "linux")
+    }
+
+    /// samples the system-wide counter and picks the top processes from the rates
+    /// [`ProcessCollector`](crate::process::ProcessCollector) computed, which it
+    /// should be called right after
+    pub fn refresh(&mut self, processes: &[ProcessStats]) {
+        if !Self::is_supported() {
+            return;
+        }
+
+        let secs = self.last_sample.elapsed().as_secs_f64().max(f64::EPSILON);
+        self.last_sample = Instant::now();
+
+        let total = read_total_switches();
+        self.per_sec = match (self.last_total, total) {
+            (Some(last), Some(total)) => Some(total.saturating_sub(last) as f64 / secs),
+            _ => None,
+        };
+        self.last_total = total;
+
+        // the first process refresh has no earlier counters, so every rate is 0
+        let mut top: Vec<ProcessSwitchRate> = processes
+            .iter()
+            .filter(|process| process.voluntary_switches + process.involuntary_switches > 0.0)
+            .map(|process| ProcessSwitchRate {
+                pid: process.pid,
+                name: process.name.clone(),
+                voluntary: process.voluntary_switches,
+                involuntary: process.involuntary_switches,
+            })
+            .collect();
+
+        top.sort_by(|a, b| (b.voluntary + b.involuntary).total_cmp(&(a.voluntary + a.involuntary)));
+        top.truncate(TOP_PROCESSES);
+
+        self.top = top;
+    }
+}
+
+/// reads the total number of context switches since boot
+///
+/// `/proc/schedstat` (version 15) is preferred; kernels built without
+/// schedstats fall back to the `ctxt` line of `/proc/stat`.
+fn read_total_switches() -> Option<u64> {
+    if let Ok(schedstat) = std::fs::read_to_string("/proc/schedstat") {
+        if schedstat.lines().next() == Some("version 15") {
+            // cpu<N> yld_count legacy sched_count ...
+            let total = schedstat
+                .lines()
+                .filter(|line| line.starts_with("cpu"))
+                .filter_map(|line| line.split_whitespace().nth(3)?.parse::<u64>().ok())
+                .sum();
+
+            return Some(total);
+        }
+    }
+
+    std::fs::read_to_string("/proc/stat")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("ctxt ")?.trim().parse().ok())
+}
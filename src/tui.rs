@@ -1,27 +1,123 @@
-use std::io::{self, stdout, Stdout};
+use std::{
+    io::{self, stdout, Stdout, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
 
 use ratatui::{
     backend::CrosstermBackend,
     crossterm::{
+        event::{
+            DisableFocusChange, EnableFocusChange, KeyboardEnhancementFlags,
+            PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        },
         execute,
-        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+        terminal::{
+            self, disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+            SetTitle,
+        },
     },
     Terminal,
 };
 
+/// whether the original window title was pushed by [`push_title`] and not popped yet
+static TITLE_PUSHED: AtomicBool = AtomicBool::new(false);
+
+/// whether [`DISAMBIGUATE_ESCAPE_CODES`] was pushed and not popped yet
+///
+/// [`DISAMBIGUATE_ESCAPE_CODES`]: KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+static KEYBOARD_PUSHED: AtomicBool = AtomicBool::new(false);
+
+/// the answer of [`supports_keyboard_enhancement`], the terminal is only asked once
+static KEYBOARD_ENHANCEMENT: OnceLock<bool> = OnceLock::new();
+
 /// A type alias for the terminal type used in this application
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
 /// Initialize the terminal
+///
+/// also installs a panic hook that restores the terminal before the panic is printed
 pub fn init() -> io::Result<Tui> {
-    execute!(stdout(), EnterAlternateScreen)?;
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore();
+        hook(info);
+    }));
+
+    execute!(stdout(), EnterAlternateScreen, EnableFocusChange)?;
     enable_raw_mode()?;
+    push_keyboard_enhancement()?;
     Terminal::new(CrosstermBackend::new(stdout()))
 }
 
 /// Restore the terminal to its original state
 pub fn restore() -> io::Result<()> {
-    execute!(stdout(), LeaveAlternateScreen)?;
+    pop_title()?;
+    pop_keyboard_enhancement()?;
+    execute!(stdout(), DisableFocusChange, LeaveAlternateScreen)?;
     disable_raw_mode()?;
     Ok(())
 }
+
+/// re-enters the TUI after [`restore`], e.g. once a launched command exited
+pub fn resume(terminal: &mut Tui) -> io::Result<()> {
+    execute!(stdout(), EnterAlternateScreen, EnableFocusChange)?;
+    enable_raw_mode()?;
+    push_keyboard_enhancement()?;
+    // the command drew over the screen ratatui remembers
+    terminal.clear()
+}
+
+/// whether the terminal speaks the kitty keyboard protocol
+///
+/// Without it Ctrl+1, 2, 3, 8, 9 and 0 arrive as other keys or not at all,
+/// e.g. Ctrl+2 as Ctrl+Space and Ctrl+3 as Esc.
+pub fn supports_keyboard_enhancement() -> bool {
+    *KEYBOARD_ENHANCEMENT.get_or_init(|| terminal::supports_keyboard_enhancement().unwrap_or(false))
+}
+
+/// asks the terminal to report modified keys unambiguously, where it can
+fn push_keyboard_enhancement() -> io::Result<()> {
+    if supports_keyboard_enhancement() && !KEYBOARD_PUSHED.swap(true, Ordering::Relaxed) {
+        execute!(
+            stdout(),
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
+    Ok(())
+}
+
+fn pop_keyboard_enhancement() -> io::Result<()> {
+    if KEYBOARD_PUSHED.swap(false, Ordering::Relaxed) {
+        execute!(stdout(), PopKeyboardEnhancementFlags)?;
+    }
+    Ok(())
+}
+
+/// saves the window title on the terminal's title stack, for [`pop_title`]
+///
+/// crossterm cannot read the title, so this uses the xterm `CSI 22 ; 0 t` extension
+pub fn push_title() -> io::Result<()> {
+    if !TITLE_PUSHED.swap(true, Ordering::Relaxed) {
+        let mut stdout = stdout();
+        stdout.write_all(b"\x1b[22;0t")?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+pub fn set_title(title: &str) -> io::Result<()> {
+    execute!(stdout(), SetTitle(title))
+}
+
+/// restores the title saved by [`push_title`], if any
+pub fn pop_title() -> io::Result<()> {
+    if TITLE_PUSHED.swap(false, Ordering::Relaxed) {
+        let mut stdout = stdout();
+        stdout.write_all(b"\x1b[23;0t")?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
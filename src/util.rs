@@ -0,0 +1,95 @@
+use ratatui::{
+    style::{Style, Stylize},
+    text::Span,
+};
+
+const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+/// Relative change beyond which [`trend_arrow`] reports a value as rising or falling
+const TREND_THRESHOLD: f64 = 0.02;
+
+/// formats a byte count using binary units, e.g. `1.5 GiB`
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// a byte count in at most 5 characters, e.g. `6.1G`, for labels that need to stay short
+pub fn format_bytes_short(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    let suffix = &UNITS[unit][..1];
+    if unit == 0 {
+        format!("{bytes}{suffix}")
+    } else if value < 10.0 {
+        format!("{value:.1}{suffix}")
+    } else {
+        format!("{value:.0}{suffix}")
+    }
+}
+
+/// formats a throughput in bytes per second, e.g. `1.5 MiB/s`
+pub fn format_bytes_per_sec(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec as u64))
+}
+
+/// formats a duration in seconds as days, hours and minutes, e.g. `3d 4h 12m`
+pub fn format_uptime(secs: u64) -> String {
+    let days = secs / 86_400;
+    let hours = secs / 3600 % 24;
+    let minutes = secs / 60 % 60;
+
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else {
+        format!("{hours}h {minutes}m")
+    }
+}
+
+/// bold and underlined if `new` moved away from `old` by more than `threshold`
+pub fn diff_style(old: f32, new: f32, threshold: f32) -> Style {
+    if (new - old).abs() > threshold {
+        Style::default().bold().underlined()
+    } else {
+        Style::default()
+    }
+}
+
+/// `↑` in red or `↓` in green if `new` moved more than 2 % away from `old`, `–` otherwise
+pub fn trend_arrow(old: f64, new: f64) -> Span<'static> {
+    let change = if old == 0.0 {
+        // any change from zero counts, `signum` alone would call 0 -> 0 rising
+        if new == 0.0 {
+            0.0
+        } else {
+            new.signum()
+        }
+    } else {
+        (new - old) / old.abs()
+    };
+
+    if change > TREND_THRESHOLD {
+        Span::from("↑").red()
+    } else if change < -TREND_THRESHOLD {
+        Span::from("↓").green()
+    } else {
+        Span::from("–").white()
+    }
+}
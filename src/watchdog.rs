@@ -0,0 +1,126 @@
+//! `--watchdog`: rerun sys-tui whenever it exits with an error
+
+use std::{
+    collections::VecDeque,
+    ffi::OsString,
+    io,
+    process::{Command, ExitStatus},
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::CommandFactory;
+
+use crate::cli::Cli;
+
+const RESTART_DELAY: Duration = Duration::from_secs(2);
+/// giving up after this many crashes within `CRASH_WINDOW`
+const MAX_CRASHES: usize = 5;
+const CRASH_WINDOW: Duration = Duration::from_secs(60);
+
+/// runs the current executable with our arguments minus `--watchdog` until it
+/// exits cleanly or is interrupted, returning the exit code for this process
+pub fn run() -> io::Result<i32> {
+    let exe = std::env::current_exe()?;
+    let args = child_args(std::env::args_os().collect());
+
+    let mut crashes: VecDeque<Instant> = VecDeque::new();
+
+    loop {
+        let status = Command::new(&exe).args(&args).status()?;
+        if status.success() {
+            return Ok(0);
+        }
+        if let Some(signal) = interrupted(status) {
+            // like a shell reports it, Ctrl+C or `kill` is how the user quits
+            return Ok(128 + signal);
+        }
+
+        let now = Instant::now();
+        crashes.push_back(now);
+        while crashes
+            .front()
+            .is_some_and(|crash| now.duration_since(*crash) > CRASH_WINDOW)
+        {
+            crashes.pop_front();
+        }
+
+        let code = status
+            .code()
+            .map_or("killed by signal".to_string(), |code| code.to_string());
+
+        if crashes.len() >= MAX_CRASHES {
+            eprintln!(
+                "sys-tui crashed (code {code}) {MAX_CRASHES} times within {}s, giving up",
+                CRASH_WINDOW.as_secs()
+            );
+            return Ok(1);
+        }
+
+        eprintln!(
+            "sys-tui crashed (code {code}), restarting in {} seconds",
+            RESTART_DELAY.as_secs()
+        );
+        thread::sleep(RESTART_DELAY);
+    }
+}
+
+/// `args` without the program name and the `--watchdog` flag
+///
+/// Only the positions clap parsed as the flag are dropped, so a `--watchdog`
+/// that is the value of another option is passed on.
+fn child_args(args: Vec<OsString>) -> Vec<OsString> {
+    let matches = Cli::command().get_matches_from(&args);
+    let flag: Vec<usize> = matches
+        .indices_of("watchdog")
+        .map(Iterator::collect)
+        .unwrap_or_default();
+
+    args.into_iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(index, _)| !flag.contains(index))
+        .map(|(_, arg)| arg)
+        .collect()
+}
+
+/// the signal that ended the child if it was SIGINT or SIGTERM, which the user sends to quit
+#[cfg(unix)]
+fn interrupted(status: ExitStatus) -> Option<i32> {
+    use nix::sys::signal::Signal;
+    use std::os::unix::process::ExitStatusExt;
+
+    status
+        .signal()
+        .filter(|signal| [Signal::SIGINT as i32, Signal::SIGTERM as i32].contains(signal))
+}
+
+#[cfg(not(unix))]
+fn interrupted(_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child_args_of(args: &[&str]) -> Vec<OsString> {
+        child_args(args.iter().map(OsString::from).collect())
+    }
+
+    #[test]
+    fn drops_only_the_watchdog_flag() {
+        assert_eq!(
+            child_args_of(&["sys-tui", "--verbose", "--watchdog", "--ping", "1.1.1.1"]),
+            ["--verbose", "--ping", "1.1.1.1"]
+        );
+    }
+
+    #[test]
+    fn keeps_watchdog_as_an_option_value() {
+        assert_eq!(
+            child_args_of(&["sys-tui", "--watchdog", "--ping=--watchdog"]),
+            ["--ping=--watchdog"]
+        );
+    }
+}
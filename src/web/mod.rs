@@ -0,0 +1,73 @@
+//! HTTP dashboard serving a static page and a Server-Sent Events stream
+
+use std::{
+    io::{self, Write},
+    net::SocketAddr,
+    thread,
+    time::Duration,
+};
+
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::snapshot::SharedSnapshot;
+
+const INDEX_HTML: &str = include_str!("index.html");
+
+/// Interval between two pushed events
+const EVENT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// binds `addr` and serves the dashboard on a background thread
+pub fn spawn(addr: SocketAddr, snapshot: SharedSnapshot) -> io::Result<()> {
+    let server = Server::http(addr).map_err(io::Error::other)?;
+
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let snapshot = snapshot.clone();
+
+            // event streams never finish, so every request gets its own thread
+            thread::spawn(move || {
+                let _ = handle(request, &snapshot);
+            });
+        }
+    });
+
+    Ok(())
+}
+
+fn handle(request: Request, snapshot: &SharedSnapshot) -> io::Result<()> {
+    match request.url() {
+        "/" | "/index.html" => {
+            let header = Header::from_bytes("Content-Type", "text/html; charset=utf-8")
+                .expect("static header is valid");
+            request.respond(Response::from_string(INDEX_HTML).with_header(header))
+        }
+        "/events" => stream_events(request, snapshot),
+        _ => request.respond(Response::from_string("not found").with_status_code(404)),
+    }
+}
+
+/// writes the SSE response by hand; tiny_http buffers chunked bodies, which would delay events
+fn stream_events(request: Request, snapshot: &SharedSnapshot) -> io::Result<()> {
+    let mut writer = request.into_writer();
+
+    write!(
+        writer,
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: text/event-stream\r\n\
+         Cache-Control: no-cache\r\n\
+         Connection: keep-alive\r\n\r\n"
+    )?;
+    writer.flush()?;
+
+    loop {
+        let json = {
+            let latest = snapshot.lock().map_err(|_| io::Error::other("poisoned"))?;
+            serde_json::to_string(&*latest)?
+        };
+
+        write!(writer, "data: {json}\n\n")?;
+        writer.flush()?;
+
+        thread::sleep(EVENT_INTERVAL);
+    }
+}
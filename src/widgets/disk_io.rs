@@ -0,0 +1,73 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    widgets::{Block, Borders, Gauge, Paragraph, Widget},
+};
+
+use crate::{disk::DiskIo, util::format_bytes_per_sec};
+
+/// Read and write gauges for the combined throughput of all partitions
+///
+/// Both gauges share one scale, the highest rate seen recently, so they can be compared.
+#[derive(Debug, Clone)]
+pub struct TotalDiskIoWidget {
+    /// `None` where the platform does not report disk I/O
+    pub total: Option<DiskIo>,
+    /// the full width of the gauges in bytes per second
+    pub peak: f64,
+}
+
+impl TotalDiskIoWidget {
+    /// rows it needs, including the border
+    pub const HEIGHT: u16 = 4;
+}
+
+impl Widget for &TotalDiskIoWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::new()
+            .title(format!(
+                "Total disk I/O (scale {})",
+                format_bytes_per_sec(self.peak)
+            ))
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(total) = self.total else {
+            Paragraph::new("not available on this system")
+                .dim()
+                .render(inner, buf);
+            return;
+        };
+
+        let gauges = [
+            ("Read ", total.read_bps, Style::new().cyan()),
+            ("Write", total.write_bps, Style::new().magenta()),
+        ];
+        for (row, (label, bps, style)) in gauges.into_iter().enumerate() {
+            let row = row as u16;
+            if row >= inner.height {
+                break;
+            }
+
+            let ratio = if self.peak > 0.0 {
+                bps / self.peak
+            } else {
+                0.0
+            };
+            Gauge::default()
+                .gauge_style(style)
+                .label(format!("{label} {}", format_bytes_per_sec(bps)))
+                .ratio(ratio.clamp(0.0, 1.0))
+                .render(
+                    Rect {
+                        y: inner.y + row,
+                        height: 1,
+                        ..inner
+                    },
+                    buf,
+                );
+        }
+    }
+}
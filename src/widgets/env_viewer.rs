@@ -0,0 +1,140 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::centered;
+
+/// Scrollable popup listing the environment of one process
+#[derive(Debug, Clone)]
+pub struct EnvViewer {
+    title: String,
+    /// `KEY=value`, sorted
+    vars: Vec<String>,
+    /// shown instead of the list, e.g. when the environment cannot be read
+    message: Option<String>,
+    filter: String,
+    searching: bool,
+    scroll: usize,
+    /// rows the last render had room for, so scrolling stops with the last row at the bottom
+    height: usize,
+}
+
+impl EnvViewer {
+    pub fn new(title: impl Into<String>, mut vars: Vec<String>, message: Option<String>) -> Self {
+        vars.sort();
+
+        EnvViewer {
+            title: title.into(),
+            vars,
+            message,
+            filter: String::new(),
+            searching: false,
+            scroll: 0,
+            height: 0,
+        }
+    }
+
+    /// handles scrolling and the `/` key prefix search, returning `false` once closed
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.searching {
+            match code {
+                KeyCode::Char(c) => self.filter.push(c),
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Enter => self.searching = false,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.searching = false;
+                }
+                _ => {}
+            }
+            self.scroll = 0;
+            return true;
+        }
+
+        let last = self.visible().count().saturating_sub(self.height.max(1));
+        match code {
+            KeyCode::Esc => return false,
+            KeyCode::Char('/') => {
+                self.filter.clear();
+                self.searching = true;
+            }
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(last),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = last,
+            _ => {}
+        }
+
+        true
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &String> {
+        self.vars.iter().filter(|var| {
+            let key = var.split_once('=').map_or(var.as_str(), |(key, _)| key);
+            key.starts_with(self.filter.as_str())
+        })
+    }
+}
+
+impl Widget for &mut EnvViewer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let popup = centered(area, area.width * 4 / 5, area.height * 4 / 5);
+
+        let mut block = Block::new()
+            .title(self.title.as_str())
+            .borders(Borders::ALL)
+            .border_style(Style::new().yellow());
+
+        if self.searching || !self.filter.is_empty() {
+            let cursor = if self.searching { "_" } else { "" };
+            block = block.title_bottom(format!("/{}{cursor}", self.filter));
+        } else {
+            block = block.title_bottom(Line::from("/ search | Esc close").dim());
+        }
+
+        let inner = block.inner(popup);
+        Clear.render(popup, buf);
+        block.render(popup, buf);
+
+        if let Some(message) = &self.message {
+            Paragraph::new(message.as_str()).red().render(inner, buf);
+            return;
+        }
+
+        let width = usize::from(inner.width);
+        // stored clamped, so the first Up after a resize or End moves the view
+        self.height = usize::from(inner.height);
+        self.scroll = self
+            .scroll
+            .min(self.visible().count().saturating_sub(self.height));
+
+        let lines: Vec<Line> = self
+            .visible()
+            .skip(self.scroll)
+            .take(usize::from(inner.height))
+            .map(|var| Line::from(truncate(var, width)))
+            .collect();
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+/// cuts `text` to `width` characters, ending in `…` if anything was cut
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::centered;
+use crate::event_log::{EventLog, Severity};
+
+/// Scrollable popup over the event log, newest entry first
+#[derive(Debug, Clone, Default)]
+pub struct EventLogView {
+    scroll: usize,
+}
+
+impl EventLogView {
+    /// handles scrolling over `len` entries, returning `false` once closed
+    pub fn handle_key(&mut self, code: KeyCode, len: usize) -> bool {
+        let last = len.saturating_sub(1);
+        match code {
+            KeyCode::Esc | KeyCode::Char('L') => return false,
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(last),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = last,
+            _ => {}
+        }
+
+        true
+    }
+
+    pub fn render(&self, log: &EventLog, area: Rect, buf: &mut Buffer) {
+        let popup = centered(area, area.width * 4 / 5, area.height * 4 / 5);
+
+        let block = Block::new()
+            .title(format!("Events ({})", log.len()))
+            .title_bottom(Line::from("↑/↓ scroll | L/Esc close").dim())
+            .borders(Borders::ALL)
+            .border_style(Style::new().yellow());
+
+        let inner = block.inner(popup);
+        Clear.render(popup, buf);
+        block.render(popup, buf);
+
+        if log.is_empty() {
+            Paragraph::new("Nothing has happened yet")
+                .dim()
+                .render(inner, buf);
+            return;
+        }
+
+        let scroll = self
+            .scroll
+            .min(log.len().saturating_sub(usize::from(inner.height)));
+
+        let lines: Vec<Line> = log
+            .entries()
+            .skip(scroll)
+            .take(usize::from(inner.height))
+            .map(|(time, severity, message)| {
+                let style = match severity {
+                    Severity::Info => Style::new().dim(),
+                    Severity::Warning => Style::new().yellow(),
+                    Severity::Critical => Style::new().red().bold(),
+                };
+                Line::from(vec![
+                    Span::from(time.format("%H:%M:%S ").to_string()).dim(),
+                    Span::styled(message.as_str(), style),
+                ])
+            })
+            .collect();
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -0,0 +1,211 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Sparkline, Widget},
+};
+use serde::{Deserialize, Serialize};
+
+/// One `[[gradient]]` entry of the config
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct GradientStop {
+    /// percent of the maximum, 0 to 100
+    at: f64,
+    /// a color name like `"blue"` or `"#ff8800"`
+    color: String,
+}
+
+/// Colors of a gradient at increasing percentages of the maximum value
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "Vec<GradientStop>", into = "Vec<GradientStop>")]
+pub struct GradientPalette {
+    /// `(threshold_pct, color)`, sorted by threshold
+    stops: Vec<(f64, Color)>,
+}
+
+/// blue → green → yellow → red, from cool low values to hot high ones
+impl Default for GradientPalette {
+    fn default() -> Self {
+        GradientPalette {
+            stops: vec![
+                (0.0, Color::Rgb(0, 0, 139)),
+                (33.0, Color::Rgb(0, 170, 0)),
+                (66.0, Color::Rgb(230, 210, 0)),
+                (100.0, Color::Rgb(255, 0, 0)),
+            ],
+        }
+    }
+}
+
+impl TryFrom<Vec<GradientStop>> for GradientPalette {
+    type Error = String;
+
+    fn try_from(stops: Vec<GradientStop>) -> Result<Self, Self::Error> {
+        if stops.is_empty() {
+            return Err("gradient needs at least one stop".to_string());
+        }
+
+        let mut palette = vec![];
+        for stop in stops {
+            if !(0.0..=100.0).contains(&stop.at) {
+                return Err(format!("gradient stop at {} is outside 0 to 100", stop.at));
+            }
+            if palette.last().is_some_and(|(at, _)| *at >= stop.at) {
+                return Err(format!(
+                    "gradient stops must be in increasing order, {} is not",
+                    stop.at
+                ));
+            }
+
+            let color = stop
+                .color
+                .parse::<Color>()
+                .map_err(|_| format!("unknown gradient color {:?}", stop.color))?;
+            palette.push((stop.at, color));
+        }
+
+        Ok(GradientPalette { stops: palette })
+    }
+}
+
+impl From<GradientPalette> for Vec<GradientStop> {
+    fn from(palette: GradientPalette) -> Self {
+        palette
+            .stops
+            .into_iter()
+            .map(|(at, color)| GradientStop {
+                at,
+                color: color.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// the color of `value` on `palette`, interpolated linearly between the two nearest stops
+///
+/// Colors without RGB values, like indexed ones, are not blended; the lower stop wins.
+pub fn gradient_color(value: u64, max: u64, palette: &GradientPalette) -> Color {
+    let pct = if max == 0 {
+        0.0
+    } else {
+        (value as f64 / max as f64 * 100.0).clamp(0.0, 100.0)
+    };
+
+    let stops = &palette.stops;
+    let upper = stops.iter().position(|(at, _)| *at >= pct);
+    let (low, high) = match upper {
+        Some(0) => return stops[0].1,
+        Some(index) => (stops[index - 1], stops[index]),
+        None => return stops[stops.len() - 1].1,
+    };
+
+    let (Some((r1, g1, b1)), Some((r2, g2, b2))) = (rgb(low.1), rgb(high.1)) else {
+        return low.1;
+    };
+    let t = (pct - low.0) / (high.0 - low.0);
+    let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+
+    Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+}
+
+/// RGB values of the named ANSI colors, as most terminal themes show them
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    Some(match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Reset | Color::Indexed(_) => return None,
+    })
+}
+
+/// A [`Sparkline`] whose bars are each colored by their value
+#[derive(Debug, Clone)]
+pub struct GradientSparkline<'a> {
+    block: Option<Block<'a>>,
+    data: &'a [u64],
+    /// the top of the chart, the largest value if unset
+    max: Option<u64>,
+    palette: &'a GradientPalette,
+}
+
+impl<'a> GradientSparkline<'a> {
+    pub fn new(data: &'a [u64], palette: &'a GradientPalette) -> Self {
+        GradientSparkline {
+            block: None,
+            data,
+            max: None,
+            palette,
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+}
+
+impl Widget for GradientSparkline<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner = self.block.as_ref().map_or(area, |block| block.inner(area));
+        // the sparkline draws one column per value from the left edge, so keep the newest
+        // values that fit, or the chart would stop moving once the history is full
+        let data = &self.data[self.data.len().saturating_sub(usize::from(inner.width))..];
+        let max = self
+            .max
+            .unwrap_or_else(|| data.iter().copied().max().unwrap_or_default());
+
+        let mut sparkline = Sparkline::default().data(data).max(max);
+        if let Some(block) = self.block {
+            sparkline = sparkline.block(block);
+        }
+        sparkline.render(area, buf);
+
+        for (column, value) in data.iter().enumerate() {
+            buf.set_style(
+                Rect {
+                    x: inner.x + column as u16,
+                    width: 1,
+                    ..inner
+                },
+                Style::new().fg(gradient_color(*value, max, self.palette)),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_draws_the_newest_values_that_fit() {
+        let data = [0, 0, 0, 0, 0, 0, 0, 10];
+        let palette = GradientPalette::default();
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buf = Buffer::empty(area);
+
+        GradientSparkline::new(&data, &palette).render(area, &mut buf);
+
+        assert_eq!(buf.get(2, 0).symbol(), "█");
+        assert_eq!(buf.get(2, 0).fg, gradient_color(10, 10, &palette));
+    }
+}
@@ -0,0 +1,93 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::centered;
+
+/// Outcome of feeding a key to a [`NumericInput`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputResult {
+    Pending,
+    Accepted(f64),
+    Cancelled,
+}
+
+/// A single-line input that accepts a number within `min..=max`
+#[derive(Debug, Clone)]
+pub struct NumericInput {
+    title: String,
+    value: String,
+    min: f64,
+    max: f64,
+    error: Option<String>,
+}
+
+impl NumericInput {
+    pub fn new(title: impl Into<String>, initial: f64, min: f64, max: f64) -> Self {
+        NumericInput {
+            title: title.into(),
+            value: initial.to_string(),
+            min,
+            max,
+            error: None,
+        }
+    }
+
+    /// handles digits, a single decimal point, Backspace, Enter and Escape
+    pub fn handle_key(&mut self, code: KeyCode) -> InputResult {
+        match code {
+            KeyCode::Char(c) if c.is_ascii_digit() => self.push(c),
+            KeyCode::Char('.') if !self.value.contains('.') => self.push('.'),
+            KeyCode::Backspace => {
+                self.value.pop();
+                self.error = None;
+            }
+            KeyCode::Enter => return self.validate(),
+            KeyCode::Esc => return InputResult::Cancelled,
+            _ => {}
+        }
+
+        InputResult::Pending
+    }
+
+    fn push(&mut self, c: char) {
+        self.value.push(c);
+        self.error = None;
+    }
+
+    fn validate(&mut self) -> InputResult {
+        match self.value.parse::<f64>() {
+            Ok(value) if (self.min..=self.max).contains(&value) => InputResult::Accepted(value),
+            _ => {
+                self.error = Some(format!("must be {}-{}", self.min, self.max));
+                InputResult::Pending
+            }
+        }
+    }
+}
+
+impl Widget for &NumericInput {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = (self.title.len() as u16 + 4).max(30);
+        let popup = centered(area, width, 3);
+
+        let mut block = Block::new()
+            .title(self.title.as_str())
+            .borders(Borders::ALL)
+            .border_style(Style::new().yellow());
+
+        if let Some(error) = &self.error {
+            block = block.title_bottom(Line::from(error.as_str()).red());
+        }
+
+        Clear.render(popup, buf);
+        Paragraph::new(format!("{}_", self.value))
+            .block(block)
+            .render(popup, buf);
+    }
+}
@@ -0,0 +1,90 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use crate::{snapshot::MemorySnapshot, util::format_bytes};
+
+/// A horizontal bar split into proportional memory segments, like htop's memory meter
+///
+/// The first row is the bar, the second row (if there is room) a legend.
+#[derive(Debug)]
+pub struct MemoryBar {
+    segments: Vec<(&'static str, u64, Color)>,
+    total: u64,
+}
+
+impl MemoryBar {
+    /// used / cached / buffers / free where the OS reports them, otherwise used / free
+    pub fn new(memory: &MemorySnapshot) -> Self {
+        let segments = match &memory.breakdown {
+            Some(breakdown) => vec![
+                ("used", breakdown.used, Color::Red),
+                ("cached", breakdown.cached, Color::Blue),
+                ("buffers", breakdown.buffers, Color::Cyan),
+                ("free", breakdown.free, Color::Green),
+            ],
+            None => vec![
+                ("used", memory.used, Color::Red),
+                (
+                    "free",
+                    memory.total.saturating_sub(memory.used),
+                    Color::Green,
+                ),
+            ],
+        };
+
+        MemoryBar {
+            segments,
+            total: memory.total,
+        }
+    }
+}
+
+impl Widget for &MemoryBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 || self.total == 0 {
+            return;
+        }
+
+        let width = u64::from(area.width);
+        let mut cumulative = 0;
+        let mut start = 0;
+
+        for (_, bytes, color) in &self.segments {
+            // place boundaries on the rounded cumulative sum so the segments always fill the bar
+            cumulative += bytes;
+            let end = ((cumulative * width + self.total / 2) / self.total).min(width) as u16;
+            let segment_width = end.saturating_sub(start);
+
+            let segment = Rect::new(area.x + start, area.y, segment_width, 1);
+            buf.set_style(segment, Style::new().bg(*color));
+
+            let label = format_bytes(*bytes);
+            if label.len() as u16 + 2 <= segment_width {
+                let x = segment.x + (segment_width - label.len() as u16) / 2;
+                buf.set_string(x, area.y, &label, Style::new().bg(*color).black());
+            }
+
+            start = end.max(start);
+        }
+
+        if area.height > 1 {
+            let legend: Vec<Span> = self
+                .segments
+                .iter()
+                .flat_map(|(name, bytes, color)| {
+                    [
+                        Span::from("■ ").fg(*color),
+                        Span::from(format!("{name} {}  ", format_bytes(*bytes))),
+                    ]
+                })
+                .collect();
+
+            Line::from(legend).render(Rect::new(area.x, area.y + 1, area.width, 1), buf);
+        }
+    }
+}
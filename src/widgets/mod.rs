@@ -0,0 +1,24 @@
+use ratatui::layout::Rect;
+
+pub mod disk_io;
+pub mod env_viewer;
+pub mod event_log;
+pub mod gradient;
+pub mod input;
+pub mod memory_bar;
+pub mod process_age;
+pub mod self_monitor;
+pub mod smaps_viewer;
+
+/// returns a `width` x `height` rect centered in `area`, clamped to its size
+pub fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
@@ -0,0 +1,115 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Direction, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Widget},
+};
+
+/// How long a process has been running, in the bins of the age histogram
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeBucket {
+    UnderMinute,
+    UnderTenMinutes,
+    UnderHour,
+    UnderDay,
+    UnderWeek,
+    OverWeek,
+}
+
+impl AgeBucket {
+    pub const ALL: [AgeBucket; 6] = [
+        AgeBucket::UnderMinute,
+        AgeBucket::UnderTenMinutes,
+        AgeBucket::UnderHour,
+        AgeBucket::UnderDay,
+        AgeBucket::UnderWeek,
+        AgeBucket::OverWeek,
+    ];
+
+    pub fn from_age(secs: u64) -> AgeBucket {
+        match secs {
+            secs if secs < 60 => AgeBucket::UnderMinute,
+            secs if secs < 10 * 60 => AgeBucket::UnderTenMinutes,
+            secs if secs < 60 * 60 => AgeBucket::UnderHour,
+            secs if secs < 24 * 60 * 60 => AgeBucket::UnderDay,
+            secs if secs < 7 * 24 * 60 * 60 => AgeBucket::UnderWeek,
+            _ => AgeBucket::OverWeek,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AgeBucket::UnderMinute => "< 1 min",
+            AgeBucket::UnderTenMinutes => "1–10 min",
+            AgeBucket::UnderHour => "10 min–1 h",
+            AgeBucket::UnderDay => "1–24 h",
+            AgeBucket::UnderWeek => "1–7 d",
+            AgeBucket::OverWeek => "> 7 d",
+        }
+    }
+}
+
+/// Horizontal bar chart of how many processes fall into each [`AgeBucket`]
+#[derive(Debug)]
+pub struct ProcessAgeWidget {
+    counts: [u64; 6],
+    /// bar highlighted for Enter, by index into [`AgeBucket::ALL`]
+    pub selected: usize,
+    /// bucket the process table is zoomed to
+    pub zoomed: Option<AgeBucket>,
+}
+
+impl ProcessAgeWidget {
+    /// bins processes by age in seconds
+    pub fn new(ages: impl IntoIterator<Item = u64>) -> Self {
+        let mut counts = [0; 6];
+        for age in ages {
+            counts[AgeBucket::from_age(age) as usize] += 1;
+        }
+
+        ProcessAgeWidget {
+            counts,
+            selected: 0,
+            zoomed: None,
+        }
+    }
+}
+
+impl Widget for &ProcessAgeWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let bars: Vec<Bar> = AgeBucket::ALL
+            .iter()
+            .zip(self.counts)
+            .enumerate()
+            .map(|(index, (bucket, count))| {
+                let mut style = Style::new().cyan();
+                if Some(*bucket) == self.zoomed {
+                    style = style.yellow();
+                }
+                if index == self.selected {
+                    style = style.bold().reversed();
+                }
+
+                Bar::default()
+                    .label(Line::from(format!("{:>10}", bucket.label())))
+                    .value(count)
+                    .style(style)
+                    .value_style(style.reversed())
+            })
+            .collect();
+
+        let title = match self.zoomed {
+            Some(bucket) => format!("Process ages (showing {})", bucket.label()),
+            None => "Process ages".to_string(),
+        };
+
+        BarChart::default()
+            .block(Block::new().title(title).borders(Borders::ALL))
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(0)
+            .data(BarGroup::default().bars(&bars))
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    layout::{Constraint, Direction, Layout},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Row, Table, Widget},
+};
+
+use crate::util::format_bytes;
+
+const WIDTH: u16 = 36;
+/// border plus the five summary lines
+const SUMMARY_HEIGHT: u16 = 7;
+
+/// sys-tui's own resource usage, drawn in the bottom-right corner
+#[derive(Debug)]
+pub struct SelfMonitor {
+    /// CPU usage in percent, `None` until the worker saw our process
+    pub cpu_usage: Option<f32>,
+    /// resident memory in bytes
+    pub memory: Option<u64>,
+    pub frames: u64,
+    /// total time spent drawing `frames`
+    pub render_time: Duration,
+    pub runtime: Duration,
+    /// per-widget render times of the last frames
+    pub widget_timings: Vec<WidgetTiming>,
+}
+
+#[derive(Debug)]
+pub struct WidgetTiming {
+    pub name: &'static str,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Widget for &SelfMonitor {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // a blank line and a header row separate the timing table from the summary
+        let table_height = match self.widget_timings.len() {
+            0 => 0,
+            rows => rows as u16 + 2,
+        };
+        let width = WIDTH.min(area.width);
+        let height = (SUMMARY_HEIGHT + table_height).min(area.height);
+        let popup = Rect {
+            x: area.right() - width,
+            y: area.bottom() - height,
+            width,
+            height,
+        };
+
+        let average = if self.frames == 0 {
+            Duration::ZERO
+        } else {
+            self.render_time / self.frames as u32
+        };
+        let runtime = self.runtime.as_secs();
+
+        let lines = vec![
+            Line::from(format!(
+                "CPU:     {}",
+                self.cpu_usage
+                    .map_or("-".to_string(), |usage| format!("{usage:.1}%"))
+            )),
+            Line::from(format!(
+                "Memory:  {}",
+                self.memory.map_or("-".to_string(), format_bytes)
+            )),
+            Line::from(format!("Frames:  {}", self.frames)),
+            Line::from(format!(
+                "Render:  {:.2} ms avg",
+                average.as_secs_f64() * 1000.0
+            )),
+            Line::from(format!(
+                "Runtime: {:02}:{:02}:{:02}",
+                runtime / 3600,
+                runtime / 60 % 60,
+                runtime % 60
+            )),
+        ];
+
+        let block = Block::new()
+            .title("sys-tui")
+            .borders(Borders::ALL)
+            .border_style(Style::new().cyan());
+        let inner = block.inner(popup);
+
+        Clear.render(popup, buf);
+        block.render(popup, buf);
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Length(5), Constraint::Min(0)])
+            .split(inner);
+        Paragraph::new(lines).render(sections[0], buf);
+
+        let mut timings: Vec<&WidgetTiming> = self.widget_timings.iter().collect();
+        timings.sort_by_key(|timing| std::cmp::Reverse(timing.mean));
+
+        let rows = timings.iter().map(|timing| {
+            Row::new(vec![
+                timing.name.to_string(),
+                timing.mean.as_micros().to_string(),
+                timing.max.as_micros().to_string(),
+            ])
+        });
+        let widths = [
+            Constraint::Min(12),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ];
+
+        Table::new(rows, widths)
+            .header(Row::new(vec!["widget", "mean µs", "max µs"]).bold())
+            .render(
+                Rect {
+                    y: sections[1].y + 1,
+                    height: sections[1].height.saturating_sub(1),
+                    ..sections[1]
+                },
+                buf,
+            );
+    }
+}
@@ -0,0 +1,86 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::KeyCode,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Row, Table, Widget},
+};
+
+use super::centered;
+use crate::{
+    process::smaps::{SegmentKind, SegmentUsage, SmapsSummary},
+    util::format_bytes,
+};
+
+/// Popup with the memory map summary of one process
+#[derive(Debug, Clone)]
+pub struct SmapsViewer {
+    title: String,
+    /// `Err` carries a message to show instead, e.g. when not permitted to read smaps
+    summary: Result<SmapsSummary, String>,
+}
+
+impl SmapsViewer {
+    pub fn new(title: impl Into<String>, summary: Result<SmapsSummary, String>) -> Self {
+        SmapsViewer {
+            title: title.into(),
+            summary,
+        }
+    }
+
+    /// returns `false` once closed
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        !matches!(code, KeyCode::Esc | KeyCode::Char('m'))
+    }
+}
+
+impl Widget for &SmapsViewer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // borders, the header, four segments and the total
+        let popup = centered(area, 60, 2 + 1 + 4 + 1);
+
+        let block = Block::new()
+            .title(self.title.as_str())
+            .title_bottom(Line::from("m/Esc close").dim())
+            .borders(Borders::ALL)
+            .border_style(Style::new().yellow());
+
+        let inner = block.inner(popup);
+        Clear.render(popup, buf);
+        block.render(popup, buf);
+
+        let summary = match &self.summary {
+            Ok(summary) => summary,
+            Err(message) => {
+                Paragraph::new(message.as_str()).red().render(inner, buf);
+                return;
+            }
+        };
+
+        let row = |label: &str, usage: SegmentUsage| {
+            Row::new(vec![
+                label.to_string(),
+                format_bytes(usage.private_rss),
+                format_bytes(usage.shared_rss),
+                format_bytes(usage.dirty),
+            ])
+        };
+
+        let mut rows: Vec<Row> = SegmentKind::ALL
+            .iter()
+            .map(|kind| row(kind.label(), summary.get(*kind)))
+            .collect();
+        rows.push(row("total", summary.total()).bold());
+
+        let widths = [
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(14),
+        ];
+        Table::new(rows, widths)
+            .header(Row::new(vec!["Segment", "Private RSS", "Shared RSS", "Dirty"]).bold())
+            .render(inner, buf);
+    }
+}
@@ -0,0 +1,417 @@
+//! Background thread that refreshes sysinfo and publishes snapshots
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, SyncSender},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use sysinfo::{
+    Components, Disks, Networks, ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind,
+};
+
+use crate::{
+    disk::{self, InodeCache},
+    dns::SharedDns,
+    net,
+    nvme::EnduranceCache,
+    ping::SharedPing,
+    process::{self, ProcessCollector, ProcessStats},
+    snapshot::{
+        CpuSnapshot, MemoryBreakdown, MemorySnapshot, SharedSnapshot, SwapIo, SystemSnapshot,
+    },
+    startup_timings::{Phase, StartupTimings},
+};
+
+/// Owns the sysinfo handles and turns them into snapshots
+struct Collector {
+    system: System,
+    networks: Networks,
+    disks: Disks,
+    /// temperature sensors, read for the CPU tab's temperature heatmap; an empty list
+    /// that is never refreshed where no sensor reports a temperature
+    components: Components,
+    nvme_endurance: EnduranceCache,
+    inodes: InodeCache,
+    processes: ProcessCollector,
+    process_interval: Duration,
+    /// when processes were last refreshed, `None` before the first refresh
+    last_process_refresh: Option<Instant>,
+    /// process list of the last process refresh, reused in between
+    process_stats: Vec<ProcessStats>,
+    /// unix timestamp in milliseconds of `process_stats`
+    processes_updated_ms: i64,
+    /// `pswpin` / `pswpout` of the previous refresh
+    last_swap_pages: Option<(u64, u64)>,
+    /// `/proc/diskstats` byte counters of the previous refresh
+    last_disk_bytes: HashMap<String, (u64, u64)>,
+    ping: Option<SharedPing>,
+    dns: Vec<SharedDns>,
+    /// per-core `/proc/stat` times of the previous refresh
+    last_cpu_times: Vec<crate::proc_stat::CpuTimes>,
+    #[cfg(feature = "perf_events")]
+    syscall_rate: crate::syscall_rate::SyscallRate,
+    #[cfg(feature = "rapl")]
+    power: crate::rapl::PowerMeter,
+    last_refresh: Instant,
+}
+
+impl Collector {
+    fn new(options: WorkerOptions, timings: &mut StartupTimings) -> Self {
+        let mut system = System::new_all();
+        timings.mark(Phase::SysinfoInit);
+
+        // usage is the difference between two samples, `new_all` took the first
+        system.refresh_cpu_all();
+        timings.mark(Phase::FirstCpuRefresh);
+
+        Collector {
+            system,
+            networks: Networks::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list(),
+            components: if options.temperatures {
+                Components::new_with_refreshed_list()
+            } else {
+                Components::new()
+            },
+            nvme_endurance: EnduranceCache::default(),
+            inodes: InodeCache::default(),
+            processes: ProcessCollector::default(),
+            process_interval: options.process_interval,
+            last_process_refresh: None,
+            process_stats: vec![],
+            processes_updated_ms: 0,
+            last_swap_pages: None,
+            last_disk_bytes: read_disk_bytes(),
+            ping: options.ping,
+            dns: options.dns,
+            last_cpu_times: crate::proc_stat::read_cpu_times().unwrap_or_default(),
+            #[cfg(feature = "perf_events")]
+            syscall_rate: crate::syscall_rate::SyscallRate::new(),
+            #[cfg(feature = "rapl")]
+            power: crate::rapl::PowerMeter::new(),
+            last_refresh: Instant::now(),
+        }
+    }
+
+    fn refresh(&mut self) -> SystemSnapshot {
+        self.system.refresh_cpu_all();
+        self.system.refresh_memory();
+        self.refresh_processes();
+        self.networks.refresh();
+        self.disks.refresh();
+        if !self.components.is_empty() {
+            self.components.refresh();
+        }
+
+        let elapsed = self.last_refresh.elapsed();
+        self.last_refresh = Instant::now();
+
+        let swap_pages = read_swap_pages();
+        let swap_io = match (self.last_swap_pages, swap_pages) {
+            (Some((last_in, last_out)), Some((pages_in, pages_out))) => Some(SwapIo {
+                pages_in: process::per_sec(last_in, pages_in, elapsed),
+                pages_out: process::per_sec(last_out, pages_out, elapsed),
+            }),
+            _ => None,
+        };
+        self.last_swap_pages = swap_pages;
+
+        let mut disks = disk::collect(&self.disks, &mut self.nvme_endurance, &mut self.inodes);
+        let disk_bytes = read_disk_bytes();
+        disk::apply_io(&mut disks, &self.last_disk_bytes, &disk_bytes, elapsed);
+        self.last_disk_bytes = disk_bytes;
+
+        let cpu_times = self.cpu_time_deltas();
+
+        let cpu_contention = (!cpu_times.is_empty())
+            .then(|| crate::proc_stat::cpu_contention(&crate::proc_stat::combined(&cpu_times)));
+
+        let load = System::load_average();
+
+        SystemSnapshot {
+            timestamp: chrono::Utc::now().timestamp(),
+            hostname: System::host_name().unwrap_or_default(),
+            uptime_secs: System::uptime(),
+            load_average: [load.one, load.five, load.fifteen],
+            cpus: self
+                .system
+                .cpus()
+                .iter()
+                .enumerate()
+                .map(|(index, cpu)| CpuSnapshot {
+                    name: cpu.name().to_string(),
+                    usage: cpu.cpu_usage(),
+                    frequency: cpu.frequency(),
+                    steal_pct: cpu_times.get(index).map(|times| times.pct(times.steal)),
+                    iowait_pct: cpu_times.get(index).map(|times| times.pct(times.iowait)),
+                })
+                .collect(),
+            cpu_contention,
+            core_temperatures: crate::cpu_temperature::core_temperatures(&self.components),
+            memory: MemorySnapshot {
+                total: self.system.total_memory(),
+                used: self.system.used_memory(),
+                total_swap: self.system.total_swap(),
+                used_swap: self.system.used_swap(),
+                breakdown: read_memory_breakdown(),
+                swap_io,
+            },
+            networks: net::collect(&self.networks, elapsed),
+            disks,
+            processes: self.process_stats.clone(),
+            processes_updated_ms: self.processes_updated_ms,
+            ping: self
+                .ping
+                .as_ref()
+                .and_then(|ping| Some(ping.lock().ok()?.clone())),
+            dns: self
+                .dns
+                .iter()
+                .filter_map(|dns| Some(dns.lock().ok()?.clone()))
+                .collect(),
+            #[cfg(feature = "perf_events")]
+            context_switches: crate::syscall_rate::SyscallRate::is_supported().then(|| {
+                crate::snapshot::ContextSwitches {
+                    per_sec: self.syscall_rate.per_sec,
+                    top: self.syscall_rate.top.clone(),
+                }
+            }),
+            #[cfg(feature = "rapl")]
+            power: self.power.read(),
+        }
+    }
+}
+
+impl Collector {
+    /// walks all processes, unless that was done less than `process_interval` ago
+    fn refresh_processes(&mut self) {
+        let elapsed = match self.last_process_refresh {
+            Some(last) if last.elapsed() < self.process_interval => return,
+            Some(last) => last.elapsed(),
+            None => Duration::ZERO,
+        };
+        self.last_process_refresh = Some(Instant::now());
+
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            ProcessRefreshKind::new()
+                .with_memory()
+                .with_cpu()
+                .with_cmd(UpdateKind::OnlyIfNotSet),
+        );
+
+        self.process_stats = self.processes.collect(&self.system, elapsed);
+        // sampled with the processes, so both honour `processes.refresh_interval_secs`
+        #[cfg(feature = "perf_events")]
+        self.syscall_rate.refresh(&self.process_stats);
+        self.processes_updated_ms = chrono::Utc::now().timestamp_millis();
+    }
+
+    /// per-core `/proc/stat` times since the previous refresh, empty where unsupported
+    fn cpu_time_deltas(&mut self) -> Vec<crate::proc_stat::CpuTimes> {
+        let Ok(times) = crate::proc_stat::read_cpu_times() else {
+            return vec![];
+        };
+
+        let deltas = times
+            .iter()
+            .zip(&self.last_cpu_times)
+            .map(|(now, earlier)| now.delta(earlier))
+            .collect();
+        self.last_cpu_times = times;
+
+        deltas
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_memory_breakdown() -> Option<MemoryBreakdown> {
+    let meminfo = crate::procfs::read_meminfo().ok()?;
+    let field = |key: &str| meminfo.get(key).copied().unwrap_or(0);
+
+    let total = field("MemTotal");
+    let free = field("MemFree");
+    let buffers = field("Buffers");
+    let cached = field("Cached") + field("SReclaimable");
+
+    Some(MemoryBreakdown {
+        used: total.saturating_sub(free + buffers + cached),
+        cached,
+        buffers,
+        free,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_memory_breakdown() -> Option<MemoryBreakdown> {
+    None
+}
+
+/// total `(pswpin, pswpout)` pages since boot
+#[cfg(target_os = "linux")]
+fn read_swap_pages() -> Option<(u64, u64)> {
+    let vmstat = crate::procfs::read_vmstat().ok()?;
+
+    Some((*vmstat.get("pswpin")?, *vmstat.get("pswpout")?))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_swap_pages() -> Option<(u64, u64)> {
+    None
+}
+
+/// total `(read, written)` bytes per block device since boot
+#[cfg(target_os = "linux")]
+fn read_disk_bytes() -> HashMap<String, (u64, u64)> {
+    crate::procfs::read_diskstats().unwrap_or_default()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_disk_bytes() -> HashMap<String, (u64, u64)> {
+    HashMap::new()
+}
+
+/// takes a single snapshot without starting the refresh thread
+///
+/// waits long enough between the first two CPU samples for usage to be meaningful
+pub fn snapshot_once() -> SystemSnapshot {
+    let mut collector = Collector::new(WorkerOptions::default(), &mut StartupTimings::default());
+    thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+
+    collector.refresh()
+}
+
+/// What the refresh thread collects and how often
+#[derive(Debug)]
+pub struct WorkerOptions {
+    pub interval: Duration,
+    /// walking all processes is expensive, so it can happen less often than `interval`
+    pub process_interval: Duration,
+    /// `--ping` results copied into every snapshot
+    pub ping: Option<SharedPing>,
+    /// `--dns-check` results copied into every snapshot
+    pub dns: Vec<SharedDns>,
+    /// whether to read the temperature sensors, see [`CapabilitySet::temperature`]
+    ///
+    /// [`CapabilitySet::temperature`]: crate::compat::CapabilitySet::temperature
+    pub temperatures: bool,
+}
+
+impl Default for WorkerOptions {
+    fn default() -> Self {
+        WorkerOptions {
+            interval: Duration::from_secs(1),
+            process_interval: Duration::from_secs(1),
+            ping: None,
+            dns: vec![],
+            temperatures: true,
+        }
+    }
+}
+
+/// Handle to the refresh thread
+#[derive(Debug, Clone)]
+pub struct Worker {
+    /// the most recent snapshot
+    pub snapshot: SharedSnapshot,
+    subscribers: Arc<Mutex<Vec<SyncSender<SystemSnapshot>>>>,
+    paused: Arc<AtomicBool>,
+    /// interrupts the wait for the next refresh
+    wake: SyncSender<()>,
+}
+
+impl Worker {
+    /// takes a first snapshot on the calling thread, then keeps refreshing every `options.interval`
+    ///
+    /// marks the sysinfo phases of startup in `timings`
+    pub fn spawn(options: WorkerOptions, timings: &mut StartupTimings) -> Worker {
+        let interval = options.interval;
+        let mut collector = Collector::new(options, timings);
+        let (wake, woken) = mpsc::sync_channel(1);
+
+        let snapshot = collector.refresh();
+        timings.mark(Phase::FirstProcessList);
+
+        let worker = Worker {
+            snapshot: Arc::new(Mutex::new(snapshot)),
+            subscribers: Arc::new(Mutex::new(vec![])),
+            paused: Arc::new(AtomicBool::new(false)),
+            wake,
+        };
+
+        let handle = worker.clone();
+        thread::spawn(move || loop {
+            // a wake-up means the refresh was resumed, so processes are due as well
+            if woken.recv_timeout(interval).is_ok() {
+                collector.last_process_refresh = None;
+            }
+            if !handle.paused.load(Ordering::Relaxed) {
+                handle.publish(collector.refresh());
+            }
+        });
+
+        worker
+    }
+
+    /// serves `snapshot` without a refresh thread, so tests draw the same data every time
+    #[cfg(test)]
+    pub fn fixed(snapshot: SystemSnapshot) -> Worker {
+        let (wake, _) = mpsc::sync_channel(1);
+
+        Worker {
+            snapshot: Arc::new(Mutex::new(snapshot)),
+            subscribers: Arc::new(Mutex::new(vec![])),
+            paused: Arc::new(AtomicBool::new(false)),
+            wake,
+        }
+    }
+
+    /// stops refreshing until resumed, which refreshes right away
+    pub fn set_paused(&self, paused: bool) {
+        let was_paused = self.paused.swap(paused, Ordering::Relaxed);
+
+        if was_paused && !paused {
+            self.refresh_now();
+        }
+    }
+
+    /// refreshes everything, processes included, without waiting for the next interval
+    pub fn refresh_now(&self) {
+        let _ = self.wake.try_send(());
+    }
+
+    /// returns a receiver that gets every new snapshot
+    ///
+    /// snapshots are dropped while the receiver still holds an unread one, so
+    /// slow subscribers never hold up the refresh thread
+    pub fn subscribe(&self) -> Receiver<SystemSnapshot> {
+        let (tx, rx) = mpsc::sync_channel(1);
+
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(tx);
+        }
+
+        rx
+    }
+
+    fn publish(&self, snapshot: SystemSnapshot) {
+        if let Ok(mut latest) = self.snapshot.lock() {
+            *latest = snapshot.clone();
+        }
+
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain(|tx| {
+                !matches!(
+                    tx.try_send(snapshot.clone()),
+                    Err(mpsc::TrySendError::Disconnected(_))
+                )
+            });
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! WebSocket server broadcasting every snapshot as JSON
+
+use std::{
+    borrow::Cow,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::Path,
+    sync::{mpsc::Receiver, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use tungstenite::{
+    protocol::{frame::coding::CloseCode, CloseFrame, WebSocketConfig},
+    Error, Message, WebSocket,
+};
+
+use crate::snapshot::SystemSnapshot;
+
+/// Bytes queued for one client before it is considered too slow and dropped
+const MAX_WRITE_BUFFER: usize = 1024 * 1024;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Clients = Arc<Mutex<Vec<Client>>>;
+
+struct Client {
+    addr: SocketAddr,
+    socket: WebSocket<TcpStream>,
+}
+
+/// Connection events, written to the `--ws-log` file if one was given
+#[derive(Clone)]
+struct ConnectionLog(Option<Arc<Mutex<File>>>);
+
+impl ConnectionLog {
+    fn open(path: Option<&Path>) -> io::Result<ConnectionLog> {
+        let file = match path {
+            Some(path) => Some(OpenOptions::new().create(true).append(true).open(path)?),
+            None => None,
+        };
+
+        Ok(ConnectionLog(file.map(|file| Arc::new(Mutex::new(file)))))
+    }
+
+    fn log(&self, addr: SocketAddr, event: &str) {
+        let Some(file) = &self.0 else {
+            return;
+        };
+
+        if let Ok(mut file) = file.lock() {
+            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+            let _ = writeln!(file, "{now} {addr} {event}");
+        }
+    }
+}
+
+/// binds `addr` and broadcasts every snapshot received on `snapshots` to all clients
+pub fn spawn(
+    addr: SocketAddr,
+    snapshots: Receiver<SystemSnapshot>,
+    log_path: Option<&Path>,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    let log = ConnectionLog::open(log_path)?;
+    let clients: Clients = Arc::new(Mutex::new(vec![]));
+
+    let accept_clients = Arc::clone(&clients);
+    let accept_log = log.clone();
+    thread::spawn(move || accept(listener, &accept_clients, &accept_log));
+
+    thread::spawn(move || {
+        for snapshot in snapshots {
+            let Ok(json) = serde_json::to_string(&snapshot) else {
+                continue;
+            };
+
+            if let Ok(mut clients) = clients.lock() {
+                clients.retain_mut(|client| broadcast(client, &json, &log));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+fn accept(listener: TcpListener, clients: &Clients, log: &ConnectionLog) {
+    for stream in listener.incoming().flatten() {
+        let Ok(addr) = stream.peer_addr() else {
+            continue;
+        };
+
+        // a client that never finishes the handshake must not block the listener
+        let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+
+        let config = WebSocketConfig {
+            write_buffer_size: 0,
+            max_write_buffer_size: MAX_WRITE_BUFFER,
+            ..Default::default()
+        };
+
+        let Ok(socket) = tungstenite::accept_with_config(stream, Some(config)) else {
+            continue;
+        };
+
+        if socket.get_ref().set_nonblocking(true).is_err() {
+            continue;
+        }
+
+        log.log(addr, "connected");
+        if let Ok(mut clients) = clients.lock() {
+            clients.push(Client { addr, socket });
+        }
+    }
+}
+
+/// sends `json` to one client, returning `false` once it should be dropped
+fn broadcast(client: &mut Client, json: &str, log: &ConnectionLog) -> bool {
+    // drain incoming frames so pings are answered and closes are noticed
+    loop {
+        match client.socket.read() {
+            Ok(Message::Close(_)) | Err(Error::ConnectionClosed | Error::AlreadyClosed) => {
+                log.log(client.addr, "disconnected");
+                return false;
+            }
+            Ok(_) => {}
+            Err(Error::Io(err)) if err.kind() == io::ErrorKind::WouldBlock => break,
+            Err(_) => {
+                log.log(client.addr, "disconnected (error)");
+                return false;
+            }
+        }
+    }
+
+    match client.socket.send(Message::text(json)) {
+        Ok(()) => true,
+        // the frame stays queued and is retried on the next flush
+        Err(Error::Io(err)) if err.kind() == io::ErrorKind::WouldBlock => true,
+        Err(Error::WriteBufferFull(_)) => {
+            let _ = client.socket.close(Some(CloseFrame {
+                code: CloseCode::Policy,
+                reason: Cow::Borrowed("client too slow"),
+            }));
+            let _ = client.socket.flush();
+            log.log(client.addr, "disconnected (too slow)");
+            false
+        }
+        Err(_) => {
+            log.log(client.addr, "disconnected (error)");
+            false
+        }
+    }
+}